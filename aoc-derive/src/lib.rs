@@ -0,0 +1,234 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Fields, GenericParam, Ident,
+    Lifetime, LitStr,
+};
+
+/// Derives `common::record::Record` for a struct from a format pattern, e.g.
+///
+/// ```ignore
+/// #[derive(Record)]
+/// #[aoc(format = "{name} = ({left}, {right})")]
+/// struct Node<'a> {
+///     name: &'a str,
+///     left: &'a str,
+///     right: &'a str,
+/// }
+/// ```
+///
+/// Every `{field}` takes the text up to the literal that follows it, or up to the
+/// end of the line, and parses it with `common::record::FromField`. `{{` and `}}`
+/// stand for literal braces. A `Vec` field can set its own separator with
+/// `#[aoc(sep = "; ")]`.
+#[proc_macro_derive(Record, attributes(aoc))]
+pub fn derive_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+struct Field<'a> {
+    ident: &'a Ident,
+    sep: Option<LitStr>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Record can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Record needs a struct with named fields",
+        ));
+    };
+
+    let format = find_attribute(&input.attrs, "format")?.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "missing the record format, add #[aoc(format = \"...\")]",
+        )
+    })?;
+    let segments = parse_format(&format)?;
+
+    let fields = named
+        .named
+        .iter()
+        .map(|f| {
+            Ok(Field {
+                ident: f.ident.as_ref().unwrap(),
+                sep: find_attribute(&f.attrs, "sep")?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    for field in &fields {
+        let uses = segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Field(name) if field.ident == name))
+            .count();
+        if uses != 1 {
+            return Err(syn::Error::new_spanned(
+                field.ident,
+                format!("`{}` must appear exactly once in the format", field.ident),
+            ));
+        }
+    }
+
+    let mut steps = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(lit) => {
+                steps.push(quote! { ::common::parse::literal(#lit).parse(input)?; });
+            }
+            Segment::Field(name) => {
+                let Some(field) = fields.iter().find(|f| f.ident == name) else {
+                    return Err(syn::Error::new_spanned(
+                        &format,
+                        format!("`{name}` in the format is not a field of the struct"),
+                    ));
+                };
+
+                let ident = field.ident;
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(lit)) => quote! { Some(#lit) },
+                    _ => quote! { None },
+                };
+                steps.push(match &field.sep {
+                    Some(sep) => quote! {
+                        let #ident = ::common::record::list_field(input, #until, #name, #sep)?;
+                    },
+                    None => quote! {
+                        let #ident = ::common::record::field(input, #until, #name)?;
+                    },
+                });
+            }
+        }
+    }
+
+    // Borrowed fields tie the record to the input, so reuse the struct's lifetime
+    // when it has one
+    let mut generics = input.generics.clone();
+    let lifetime = match generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime: Lifetime = parse_quote!('__input);
+            generics
+                .params
+                .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+            lifetime
+        }
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let idents = fields.iter().map(|f| f.ident);
+
+    Ok(quote! {
+        impl #impl_generics ::common::record::Record<#lifetime> for #name #ty_generics #where_clause {
+            fn parse_record(
+                input: &mut ::common::parse::Input<#lifetime>,
+            ) -> ::common::parse::ParseResult<Self> {
+                use ::common::parse::Parser as _;
+                #(#steps)*
+                Ok(Self { #(#idents),* })
+            }
+        }
+
+        impl #impl_generics ::common::record::FromField<#lifetime> for #name #ty_generics #where_clause {
+            fn from_field(s: &#lifetime str) -> Result<Self, String> {
+                ::common::record::from_field(s, #name_str)
+            }
+        }
+    })
+}
+
+// Value of `#[aoc(key = "...")]` among `attrs`
+fn find_attribute(attrs: &[Attribute], key: &str) -> syn::Result<Option<LitStr>> {
+    let mut value = None;
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                value = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error(format!("unknown aoc attribute, expected `{key}`")))
+            }
+        })?;
+    }
+
+    Ok(value)
+}
+
+fn parse_format(format: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |message: &str| syn::Error::new_spanned(format, message);
+
+    let text = format.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(error("unclosed `{` in format")),
+                    }
+                }
+
+                if name.is_empty() {
+                    return Err(error("empty `{}` in format, name a field"));
+                }
+
+                if literal.is_empty() {
+                    if let Some(Segment::Field(previous)) = segments.last() {
+                        return Err(error(&format!(
+                            "`{previous}` and `{name}` need some literal text between them"
+                        )));
+                    }
+                } else {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => {
+                return Err(error(
+                    "unmatched `}` in format, use `}}` for a literal brace",
+                ))
+            }
+            _ => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
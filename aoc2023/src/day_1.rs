@@ -58,32 +58,3 @@ fn extract_digits(line: &str) -> (u32, u32) {
     }
     (first.unwrap(), last)
 }
-
-#[cfg(test)]
-mod test {
-    use super::Day1;
-    use common::{Answer, Solution};
-
-    const CASE_A: &str = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-
-    const CASE_B: &str = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-
-    #[test]
-    fn case_a() {
-        assert_eq!(Day1.part_one(CASE_A), Answer::U32(142));
-    }
-
-    #[test]
-    fn case_b() {
-        assert_eq!(Day1.part_two(CASE_B), Answer::U32(281))
-    }
-}
@@ -1,6 +1,8 @@
 use crate::day_10::Tile::StartingPosition;
+use common::polygon;
+#[cfg(feature = "viz")]
+use common::viz::{Canvas, Rgb, Visualize};
 use common::{Answer, Solution};
-use std::collections::HashSet;
 use std::ops::{Add, Mul};
 
 #[derive(Debug, Default)]
@@ -16,14 +18,6 @@ impl Maze {
         }
         self.tiles.get(point.y as usize)?.get(point.x as usize)
     }
-
-    fn width(&self) -> usize {
-        self.tiles.first().map(|row| row.len()).unwrap_or(0)
-    }
-
-    fn height(&self) -> usize {
-        self.tiles.len()
-    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -205,47 +199,6 @@ fn find_loop(maze: &Maze) -> Vec<Point> {
     path
 }
 
-fn determine_start_tile(maze: &Maze) -> Tile {
-    let start = maze.start_position;
-    let mut connections = Vec::new();
-
-    for dir in [
-        Direction::Up,
-        Direction::Down,
-        Direction::Left,
-        Direction::Right,
-    ] {
-        let next = dir.next_point(&start);
-        if let Some(tile) = maze.get(&next) {
-            match tile {
-                Tile::Ground => continue,
-                _ => {
-                    if tile.connects_to(dir.opposite()) {
-                        connections.push(dir);
-                    }
-                }
-            }
-        }
-    }
-
-    connections.sort_by_key(|d| match d {
-        Direction::Up => 0,
-        Direction::Down => 1,
-        Direction::Left => 2,
-        Direction::Right => 3,
-    });
-
-    match (connections.first(), connections.get(1)) {
-        (Some(&Direction::Up), Some(&Direction::Down)) => Tile::VerticalPipe,
-        (Some(&Direction::Left), Some(&Direction::Right)) => Tile::HorizontalPipe,
-        (Some(&Direction::Up), Some(&Direction::Right)) => Tile::NorthEastBend,
-        (Some(&Direction::Up), Some(&Direction::Left)) => Tile::NorthWestBend,
-        (Some(&Direction::Down), Some(&Direction::Left)) => Tile::SouthWestBend,
-        (Some(&Direction::Down), Some(&Direction::Right)) => Tile::SouthEastBend,
-        _ => Tile::StartingPosition,
-    }
-}
-
 pub struct Day10;
 
 impl Solution for Day10 {
@@ -260,58 +213,46 @@ impl Solution for Day10 {
     }
 
     fn part_two(&self, input: &str) -> Answer {
-        let mut maze = parse(input);
-        let loop_path = find_loop(&maze);
-        let loop_set: HashSet<Point> = loop_path.iter().copied().collect();
-
-        // Replace starting position with actual tile
-        let start_tile = determine_start_tile(&maze);
-        maze.tiles[maze.start_position.y as usize][maze.start_position.x as usize] = start_tile;
-
-        let mut count: usize = 0;
-
-        // Use scanline algorithm
-        for y in 0..maze.height() {
-            let mut inside = false;
-            let mut last_corner: Option<Direction> = None;
-
-            for x in 0..maze.width() {
-                let point = Point::new(x as i64, y as i64);
-
-                if loop_set.contains(&point) {
-                    let tile = maze.get(&point).unwrap();
-
-                    match tile {
-                        Tile::VerticalPipe => {
-                            inside = !inside;
-                        }
-                        Tile::NorthEastBend => {
-                            last_corner = Some(Direction::Up);
-                        }
-                        Tile::SouthEastBend => {
-                            last_corner = Some(Direction::Down);
-                        }
-                        Tile::NorthWestBend => {
-                            if last_corner == Some(Direction::Down) {
-                                inside = !inside;
-                            }
-                            last_corner = None;
-                        }
-                        Tile::SouthWestBend => {
-                            if last_corner == Some(Direction::Up) {
-                                inside = !inside;
-                            }
-                            last_corner = None;
-                        }
-                        _ => {}
-                    }
-                } else if inside {
-                    count += 1;
-                }
+        let maze = parse(input);
+        let vertices = find_loop(&maze)
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect::<Vec<_>>();
+
+        // The loop runs through tile centres, so the enclosed tiles are its interior lattice points
+        (polygon::interior_points(&vertices) as usize).into()
+    }
+}
+
+#[cfg(feature = "viz")]
+impl Visualize for Day10 {
+    /// The loop in yellow, the tiles it encloses in green and the start in red
+    fn visualize(&self, input: &str) -> Canvas {
+        let maze = parse(input);
+        let path = find_loop(&maze);
+        let vertices = path.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        let polygon = polygon::RectilinearPolygon::new(&vertices)
+            .expect("the loop only moves between neighbouring tiles");
+
+        let width = maze.tiles.iter().map(Vec::len).max().unwrap_or(0);
+        let mut canvas = Canvas::new(width, maze.tiles.len(), Rgb(30, 30, 40));
+
+        // Every covered tile not on the loop is enclosed
+        for y in 0..maze.tiles.len() {
+            for (start, end) in polygon.row_coverage(y as i64) {
+                canvas.fill_rect((start as usize, y), (end as usize, y), Rgb::GREEN);
             }
         }
+        for p in &path {
+            canvas.set(p.x as usize, p.y as usize, Rgb::YELLOW);
+        }
+        canvas.set(
+            maze.start_position.x as usize,
+            maze.start_position.y as usize,
+            Rgb::RED,
+        );
 
-        count.into()
+        canvas
     }
 }
 
@@ -338,53 +279,25 @@ fn parse(input: &str) -> Maze {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "viz"))]
 mod test {
     use crate::day_10::Day10;
-    use common::Solution;
-
-    const CASE_A: &str = ".....
-.S-7.
-.|.|.
-.L-J.
-.....";
-
-    const CASE_B: &str = "..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-
-    const CASE_C: &str = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-
-    const CASE_D: &str = ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...";
+    use common::viz::{Rgb, Visualize};
 
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day10.part_one(CASE_A), 4usize.into());
-        assert_eq!(Day10.part_one(CASE_B), 8usize.into());
-    }
+    const CASE_C: &str = include_str!("../../data/2023/examples/10-3.txt");
 
     #[test]
-    fn test_part_two() {
-        assert_eq!(Day10.part_two(CASE_C), 4usize.into());
-        assert_eq!(Day10.part_two(CASE_D), 8usize.into());
+    fn test_visualize() {
+        let canvas = Day10.visualize(CASE_C);
+        let count = |colour| {
+            (0..canvas.height())
+                .flat_map(|y| (0..canvas.width()).map(move |x| (x, y)))
+                .filter(|&(x, y)| canvas.get(x, y) == Some(colour))
+                .count()
+        };
+
+        assert_eq!(count(Rgb::GREEN), 4);
+        assert_eq!(count(Rgb::YELLOW) + count(Rgb::RED), 46);
+        assert_eq!(canvas.get(1, 1), Some(Rgb::RED));
     }
 }
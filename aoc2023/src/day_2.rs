@@ -1,3 +1,4 @@
+use common::record::{self, records, FromField, Record};
 use common::{Answer, Solution};
 
 #[derive(Debug, Default)]
@@ -21,6 +22,36 @@ impl CubeSet {
     }
 }
 
+impl<'a> FromField<'a> for CubeSet {
+    fn from_field(s: &'a str) -> Result<Self, String> {
+        let mut cube_set = CubeSet::default();
+        for cubes in record::list::<Cubes>(s, ",")? {
+            match cubes.color {
+                "red" => cube_set.red += cubes.count,
+                "green" => cube_set.green += cubes.count,
+                "blue" => cube_set.blue += cubes.count,
+                color => return Err(format!("red, green or blue, not {color:?}")),
+            }
+        }
+        Ok(cube_set)
+    }
+}
+
+#[derive(Record)]
+#[aoc(format = "{count} {color}")]
+struct Cubes<'a> {
+    count: u32,
+    color: &'a str,
+}
+
+#[derive(Record)]
+#[aoc(format = "Game {id}: {sets}")]
+struct Game {
+    id: usize,
+    #[aoc(sep = ";")]
+    sets: Vec<CubeSet>,
+}
+
 const VALID_GAME: CubeSet = CubeSet {
     red: 12,
     green: 13,
@@ -34,22 +65,23 @@ impl Solution for Day2 {
         "Day 2".into()
     }
     fn part_one(&self, input: &str) -> Answer {
-        parse_lines(input)
+        records::<Game>(input)
+            .unwrap()
             .iter()
-            .enumerate()
-            .filter(|(_, games)| games.iter().all(|game| game.is_possible()))
-            .map(|i| i.0 + 1)
+            .filter(|game| game.sets.iter().all(CubeSet::is_possible))
+            .map(|game| game.id)
             .sum::<usize>()
             .into()
     }
 
     fn part_two(&self, input: &str) -> Answer {
-        parse_lines(input)
+        records::<Game>(input)
+            .unwrap()
             .iter()
-            .map(|games| {
+            .map(|game| {
                 let mut min_count = CubeSet::default();
-                for game in games {
-                    min_count = min_count.get_min_count_to_possible(game);
+                for set in &game.sets {
+                    min_count = min_count.get_min_count_to_possible(set);
                 }
                 min_count.red * min_count.green * min_count.blue
             })
@@ -57,59 +89,3 @@ impl Solution for Day2 {
             .into()
     }
 }
-
-fn parse_lines(input: &str) -> Vec<Vec<CubeSet>> {
-    input
-        .lines()
-        .map(|line| {
-            let cubes = line.split_once(':').unwrap().1;
-            let mut sets = vec![];
-            for set in cubes.split(';') {
-                let mut cube_set = CubeSet::default();
-                for cube in set.trim().split(',') {
-                    let mut iter = cube.split_whitespace();
-                    let count = iter.next().unwrap().parse::<u32>().unwrap();
-                    let color = iter.next().unwrap();
-                    match color {
-                        "red" => cube_set.red += count,
-                        "green" => cube_set.green += count,
-                        "blue" => cube_set.blue += count,
-                        _ => unreachable!(),
-                    }
-                }
-                sets.push(cube_set)
-            }
-            sets
-        })
-        .collect::<Vec<Vec<CubeSet>>>()
-}
-
-#[cfg(test)]
-mod test {
-    use super::Day2;
-    use common::{Answer, Solution};
-
-    const CASE_A: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-";
-
-    const CASE_B: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day2.part_one(CASE_A), Answer::USize(8))
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day2.part_two(CASE_B), Answer::U32(2286))
-    }
-}
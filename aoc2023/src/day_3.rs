@@ -122,30 +122,3 @@ fn parse(input: &str) -> ParsedInput {
 
     ParsedInput { gears, ratios }
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_3::Day3;
-    use common::{Answer, Solution};
-
-    const CASE_A: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day3.part_one(CASE_A), Answer::U32(4361))
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day3.part_two(CASE_A), Answer::U32(467835))
-    }
-}
@@ -1,3 +1,4 @@
+use common::record::{records, Record};
 use common::{Answer, Solution};
 use std::collections::HashMap;
 
@@ -9,7 +10,8 @@ impl Solution for Day4 {
     }
 
     fn part_one(&self, input: &str) -> Answer {
-        parse(input)
+        records::<Card>(input)
+            .unwrap()
             .iter()
             .map(|x| x.get_winning_numbers())
             .filter(|x| *x > 0)
@@ -19,7 +21,7 @@ impl Solution for Day4 {
     }
 
     fn part_two(&self, input: &str) -> Answer {
-        let cards = parse(input);
+        let cards = records::<Card>(input).unwrap();
 
         let mut copies: HashMap<usize, usize> = HashMap::new();
 
@@ -41,7 +43,8 @@ impl Solution for Day4 {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Record)]
+#[aoc(format = "Card {number}: {winning} | {scratch}")]
 struct Card {
     number: usize,
     winning: Vec<u32>,
@@ -56,48 +59,3 @@ impl Card {
             .count()
     }
 }
-
-fn parse(input: &str) -> Vec<Card> {
-    let mut cards = vec![];
-    for (index, input_line) in input.lines().enumerate() {
-        let (_, line) = input_line.split_once(":").unwrap();
-        let (winning_numbers, scratch_numbers) = line.trim().split_once("|").unwrap();
-        let winning: Vec<u32> = winning_numbers
-            .split_whitespace()
-            .map(|x| x.parse().unwrap())
-            .collect();
-        let scratch: Vec<u32> = scratch_numbers
-            .split_whitespace()
-            .map(|x| x.parse().unwrap())
-            .collect();
-        cards.push(Card {
-            number: index + 1,
-            winning,
-            scratch,
-        })
-    }
-    cards
-}
-
-#[cfg(test)]
-mod test {
-    use crate::day_4::Day4;
-    use common::{Answer, Solution};
-
-    const CASE_A: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day4.part_one(CASE_A), Answer::U32(13))
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day4.part_two(CASE_A), Answer::USize(30))
-    }
-}
@@ -1,5 +1,6 @@
 use common::{Answer, Solution};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::ops::Range;
 
 pub struct Day5;
 
@@ -21,17 +22,16 @@ impl Solution for Day5 {
         min.into()
     }
 
-    // TODO: optimize part two and fix off-by-one bug
     fn part_two(&self, input: &str) -> Answer {
         let RangeResult { seeds, mappings } = parse(input);
         let seed_ranges = seeds
             .chunks_exact(2)
-            .flat_map(|chunk| chunk[0]..chunk[0] + chunk[1])
+            .map(|chunk| chunk[0]..chunk[0] + chunk[1])
             .collect::<Vec<_>>();
 
-        seed_ranges
-            .par_iter()
-            .map(|s| find_location(&mappings, *s))
+        find_location_ranges(&mappings, seed_ranges)
+            .iter()
+            .map(|r| r.start)
             .min()
             .unwrap()
             .into()
@@ -103,6 +103,31 @@ impl CategoryMap {
             .find_map(|x| x.convert(value))
             .unwrap_or(value)
     }
+
+    // Split every input range at the conversion boundaries so each piece is
+    // either fully covered by a single conversion or not covered at all
+    fn transform_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let mut pending = ranges;
+        let mut converted = Vec::new();
+
+        for conversion in &self.ranges {
+            let mut unmatched = Vec::new();
+
+            for range in pending {
+                let (before, overlap, after) = conversion.split(&range);
+
+                unmatched.extend(before);
+                unmatched.extend(after);
+                converted.extend(overlap);
+            }
+
+            pending = unmatched;
+        }
+
+        // Whatever no conversion claimed maps to itself
+        converted.extend(pending);
+        converted
+    }
 }
 
 // Step through the maps and until we find the location
@@ -110,6 +135,12 @@ fn find_location(maps: &[CategoryMap], location: u64) -> u64 {
     maps.iter().fold(location, |loc, map| map.transform(loc))
 }
 
+// Same as find_location, but pushes whole ranges through the maps at once
+fn find_location_ranges(maps: &[CategoryMap], ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    maps.iter()
+        .fold(ranges, |ranges, map| map.transform_ranges(ranges))
+}
+
 #[derive(Default, Debug)]
 struct Conversion {
     destination: u64,
@@ -118,67 +149,77 @@ struct Conversion {
 }
 
 impl Conversion {
+    fn bounds(&self) -> Range<u64> {
+        self.source..self.source + self.length
+    }
+
     fn convert(&self, location: u64) -> Option<u64> {
         // Check if location is within range
-        let lower_bound = self.source;
-        let upper_bound = self.source + self.length;
-        let bounds = lower_bound..=upper_bound;
-
-        if !bounds.contains(&location) {
+        if !self.bounds().contains(&location) {
             return None;
         }
 
         Some(self.destination + location - self.source)
     }
+
+    // Returns the parts of the range below the source range, the converted
+    // overlap and the part above it. Empty parts are returned as None
+    fn split(&self, range: &Range<u64>) -> SplitRange {
+        let bounds = self.bounds();
+
+        let before = range.start..range.end.min(bounds.start);
+        let overlap = range.start.max(bounds.start)..range.end.min(bounds.end);
+        let after = range.start.max(bounds.end)..range.end;
+
+        let overlap = (!overlap.is_empty()).then(|| {
+            self.destination + overlap.start - self.source
+                ..self.destination + overlap.end - self.source
+        });
+
+        (
+            (!before.is_empty()).then_some(before),
+            overlap,
+            (!after.is_empty()).then_some(after),
+        )
+    }
 }
 
+type SplitRange = (Option<Range<u64>>, Option<Range<u64>>, Option<Range<u64>>);
+
 #[cfg(test)]
 mod test {
-    use crate::day_5::Day5;
-    use common::Solution;
-
-    const CASE_A: &str = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4
-";
+    use crate::day_5::Conversion;
 
     #[test]
-    fn test_part_one() {
-        assert_eq!(Day5.part_one(CASE_A), 35u64.into())
+    fn test_convert() {
+        let conversion = Conversion {
+            destination: 50,
+            source: 98,
+            length: 2,
+        };
+
+        assert_eq!(conversion.convert(97), None);
+        assert_eq!(conversion.convert(98), Some(50));
+        assert_eq!(conversion.convert(99), Some(51));
+        assert_eq!(conversion.convert(100), None);
     }
 
     #[test]
-    fn test_part_two() {
-        assert_eq!(Day5.part_two(CASE_A), 46u64.into())
+    fn test_split() {
+        let conversion = Conversion {
+            destination: 52,
+            source: 50,
+            length: 48,
+        };
+
+        assert_eq!(
+            conversion.split(&(40..60)),
+            (Some(40..50), Some(52..62), None)
+        );
+        assert_eq!(
+            conversion.split(&(90..110)),
+            (None, Some(92..100), Some(98..110))
+        );
+        assert_eq!(conversion.split(&(0..10)), (Some(0..10), None, None));
     }
 }
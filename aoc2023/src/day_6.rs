@@ -1,3 +1,4 @@
+use common::math;
 use common::{Answer, Solution};
 use itertools::Itertools;
 
@@ -10,10 +11,12 @@ struct Race {
 }
 
 impl Race {
+    // Holding for x goes (time - x) * SPEED * x, a downward parabola in x
     fn get_winning_ways(&self) -> usize {
-        (0..self.time)
-            .filter(|x| (self.time - x) * SPEED * x > self.distance)
-            .count()
+        let (time, distance) = (self.time as i64, self.distance as i64);
+        let speed = SPEED as i64;
+
+        math::count_quadratic_above(-speed, speed * time, 0, distance, 0..=time) as usize
     }
 }
 
@@ -71,20 +74,17 @@ fn parse_two(input: &str) -> Race {
 
 #[cfg(test)]
 mod test {
-    use crate::day_6::Day6;
-    use common::Solution;
-
-    const CASE_A: &str = "Time:      7  15   30
-Distance:  9  40  200
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day6.part_one(CASE_A), 288usize.into())
-    }
+    use crate::day_6::{Race, SPEED};
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day6.part_two(CASE_A), 71503usize.into())
+    proptest! {
+        #[test]
+        fn prop_winning_ways(time in 0u64..2000, distance in 0u64..1_000_000) {
+            let race = Race { time, distance };
+            let brute_force = (0..time)
+                .filter(|x| (time - x) * SPEED * x > distance)
+                .count();
+            prop_assert_eq!(race.get_winning_ways(), brute_force);
+        }
     }
 }
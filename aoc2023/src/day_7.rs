@@ -148,26 +148,3 @@ fn parse(input: &str, card_sequence: String) -> Vec<CamelCard> {
 
     hands
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_7::Day7;
-    use common::Solution;
-
-    const CASE_A: &str = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day7.part_one(CASE_A), 6440usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day7.part_two(CASE_A), 5905usize.into())
-    }
-}
@@ -1,5 +1,7 @@
-use common::{lcm, Answer, Solution};
-use std::collections::HashMap;
+use common::cycle::{self, Hits};
+use common::intern::Graph;
+use common::record::{records, Record};
+use common::{Answer, Solution};
 
 #[derive(Debug)]
 enum Instruction {
@@ -17,18 +19,29 @@ impl From<char> for Instruction {
     }
 }
 
+#[derive(Debug, Record)]
+#[aoc(format = "{name} = ({left}, {right})")]
+struct Node<'a> {
+    name: &'a str,
+    left: &'a str,
+    right: &'a str,
+}
+
+// Every node has exactly two edges, left then right
 #[derive(Default, Debug)]
 struct InstructionMap<'a> {
     instructions: Vec<Instruction>,
-    nodes: HashMap<&'a str, (&'a str, &'a str)>,
+    nodes: Graph<'a>,
 }
 
-impl<'a> InstructionMap<'a> {
-    fn get(&'a self, pos: &'a str, instruction: &Instruction) -> &'a str {
-        let (left, right) = self.nodes.get(pos).unwrap();
+impl InstructionMap<'_> {
+    fn get(&self, pos: u32, instruction: &Instruction) -> u32 {
+        let [left, right] = self.nodes.neighbors(pos) else {
+            unreachable!()
+        };
         match instruction {
-            Instruction::Left => left,
-            Instruction::Right => right,
+            Instruction::Left => *left,
+            Instruction::Right => *right,
         }
     }
 }
@@ -44,13 +57,14 @@ impl Solution for Day8 {
         let map = parse(input);
 
         let mut i = 0;
-        let mut pos = "AAA";
+        let mut pos = map.nodes.id("AAA").unwrap();
+        let end = map.nodes.id("ZZZ").unwrap();
 
         loop {
             pos = map.get(pos, &map.instructions[i % map.instructions.len()]);
             i += 1;
 
-            if pos == "ZZZ" {
+            if pos == end {
                 break;
             }
         }
@@ -63,36 +77,40 @@ impl Solution for Day8 {
 
         let start_locations = map
             .nodes
-            .keys()
-            .filter(|x| x.ends_with('A'))
-            .cloned()
-            .collect::<Vec<&str>>();
-
-        let mut cycles = Vec::new();
-
-        for start in start_locations {
-            let mut pos = start;
-            let mut cycle_len = 0;
-            let mut i = 0;
-            loop {
-                pos = map.get(pos, &map.instructions[i % map.instructions.len()]);
-                i += 1;
-
-                cycle_len += 1;
-                if pos.ends_with('Z') {
-                    cycles.push(cycle_len);
-                    break;
-                }
-            }
+            .nodes()
+            .filter(|(_, label)| label.ends_with('A'))
+            .map(|(id, _)| id)
+            .collect::<Vec<u32>>();
+
+        let mut is_end = vec![false; map.nodes.len()];
+        for (id, label) in map.nodes.nodes() {
+            is_end[id as usize] = label.ends_with('Z');
         }
 
-        cycles
+        let hits = start_locations
             .into_iter()
-            .fold(1, |acc: usize, s: usize| lcm(acc, s))
+            .map(|start| ghost_hits(&map, &is_end, start))
+            .collect::<Vec<Hits>>();
+
+        cycle::first_common_hit(&hits)
+            .expect("the ghosts line up too late to count")
+            .expect("the ghosts never all end on a Z at once")
             .into()
     }
 }
 
+// A ghost's state is its node together with where it is in the instruction list,
+// so it is only guaranteed to repeat once both line up again. `is_end` is indexed by
+// node id
+fn ghost_hits(map: &InstructionMap, is_end: &[bool], start: u32) -> Hits {
+    let len = map.instructions.len();
+
+    cycle::find((start, 0), |&(pos, i)| {
+        (map.get(pos, &map.instructions[i]), (i + 1) % len)
+    })
+    .hits(|&(pos, _)| is_end[pos as usize])
+}
+
 fn parse(input: &'_ str) -> InstructionMap<'_> {
     let (instructions, node_list) = input.split_once("\n\n").unwrap();
     let instructions = instructions
@@ -100,56 +118,15 @@ fn parse(input: &'_ str) -> InstructionMap<'_> {
         .map(|x| x.into())
         .collect::<Vec<Instruction>>();
 
-    let mut nodes = HashMap::new();
-
-    for item in node_list.lines() {
-        let (node_name, children_nodes) = item.split_once(" = ").unwrap();
-        let (left, right) = children_nodes
-            .trim_start_matches('(')
-            .trim_end_matches(')')
-            .split_once(", ")
-            .unwrap();
-
-        nodes.insert(node_name.trim(), (left, right));
-    }
+    let nodes = Graph::from_adjacency(
+        records::<Node>(node_list)
+            .unwrap()
+            .into_iter()
+            .map(|node| (node.name, [node.left, node.right])),
+    );
 
     InstructionMap {
         instructions,
         nodes,
     }
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_8::Day8;
-    use common::Solution;
-
-    const CASE_A: &str = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)
-";
-
-    const CASE_B: &str = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day8.part_one(CASE_A), 6usize.into());
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day8.part_two(CASE_B), 6usize.into());
-    }
-}
@@ -21,3 +21,7 @@ pub const ALL: &[&dyn common::Solution] = &[
     &day_9::Day9,
     &day_10::Day10,
 ];
+
+/// Days that can draw their solution, by day number
+#[cfg(feature = "viz")]
+pub const VISUALIZATIONS: &[(u32, &dyn common::viz::Visualize)] = &[(10, &day_10::Day10)];
@@ -1,3 +1,4 @@
+use common::ints::ints;
 use common::{Answer, Solution};
 use itertools::Itertools;
 
@@ -32,15 +33,7 @@ impl Solution for Day2 {
 }
 
 fn parse(input: &str) -> Vec<Vec<i64>> {
-    input
-        .lines()
-        .map(str::trim)
-        .map(|line| {
-            line.split_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect()
-        })
-        .collect()
+    input.lines().map(|line| ints(line).collect()).collect()
 }
 
 fn tolerates_one_failure(input: &Vec<i64>, skip: Option<usize>) -> bool {
@@ -66,27 +59,3 @@ fn tolerates_one_failure(input: &Vec<i64>, skip: Option<usize>) -> bool {
         _ => false,
     }
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_2::Day2;
-    use common::Solution;
-
-    const CASE_A: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day2.part_one(CASE_A), 2i64.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day2.part_two(CASE_A), 4usize.into())
-    }
-}
@@ -1,3 +1,4 @@
+use common::parse::{delimited, either, literal, map, separated_pair, unsigned, Input, Parser};
 use common::{Answer, Solution};
 use regex::Regex;
 
@@ -41,96 +42,32 @@ fn parse_a(input: &str) -> Vec<Vec<usize>> {
         .collect()
 }
 
-struct Parser {
-    chars: Vec<char>,
-    idx: usize,
-}
-
-impl Parser {
-    pub fn new(input: &str) -> Self {
-        Parser {
-            chars: input.chars().collect(),
-            idx: 0,
-        }
-    }
-
-    pub fn expect(&mut self, str: &str) -> bool {
-        let valid = self.idx + str.len() < self.chars.len()
-            && self.chars[self.idx..self.idx + str.len()]
-                .iter()
-                .zip(str.chars())
-                .all(|(&a, b)| a == b);
-
-        if valid {
-            self.idx += str.len();
-        }
-
-        valid
-    }
-
-    pub fn number(&mut self) -> Option<usize> {
-        let mut working = String::new();
-        while self.chars[self.idx].is_ascii_digit() && self.idx < self.chars.len() {
-            working.push(self.chars[self.idx]);
-            self.idx += 1;
-        }
-        working.parse::<usize>().ok()
-    }
-
-    pub fn advance(&mut self, count: usize) {
-        self.idx += count;
-    }
-
-    pub fn is_eof(&self) -> bool {
-        self.idx >= self.chars.len()
-    }
-}
-
 fn parse_b(input: &str) -> Vec<Vec<usize>> {
     let mut out = vec![];
 
-    let mut parser = Parser::new(input);
+    let mul = delimited(
+        literal("mul("),
+        separated_pair(unsigned::<usize>(), literal(","), unsigned()),
+        literal(")"),
+    );
+    let enable = either(
+        map(literal("do()"), |_| true),
+        map(literal("don't()"), |_| false),
+    );
+
+    let mut input = Input::new(input);
     let mut active = true;
 
-    while !parser.is_eof() {
-        active |= parser.expect("do()");
-        active &= !parser.expect("don't()");
-
-        if parser.expect("mul(") {
-            let Some(a) = parser.number() else { continue };
-            if !parser.expect(",") {
-                continue;
-            }
-            let Some(b) = parser.number() else { continue };
-            if !parser.expect(")") {
-                continue;
-            }
+    while !input.is_eof() {
+        if let Ok(enabled) = enable.parse(&mut input) {
+            active = enabled;
+        } else if let Ok((a, b)) = mul.parse(&mut input) {
             if active {
                 out.push(vec![a, b]);
             }
         } else {
-            parser.advance(1);
+            input.advance(1);
         }
     }
     out
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_3::Day3;
-    use common::Solution;
-
-    const CASE_A: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
-";
-    const CASE_B: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
-";
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day3.part_one(CASE_A), 161usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day3.part_two(CASE_B), 48usize.into())
-    }
-}
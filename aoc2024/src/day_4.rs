@@ -117,41 +117,3 @@ fn is_xmas_pattern(grid: &[Vec<char>], row: i64, col: i64) -> bool {
 fn parse(input: &str) -> Vec<Vec<char>> {
     input.lines().map(|line| line.chars().collect()).collect()
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_4::Day4;
-    use common::Solution;
-
-    const CASE_A: &str = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX
-";
-    const CASE_B: &str = ".M.S......
-..A..MSMS.
-.M.S.MAA..
-..A.ASMSM.
-.M.S.M....
-..........
-S.S.S.S.S.
-.A.A.A.A..
-M.M.M.M.M.
-..........
-";
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day4.part_one(CASE_A), 18i64.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day4.part_two(CASE_B), 9usize.into())
-    }
-}
@@ -88,34 +88,3 @@ fn parse(input: &str) -> Vec<Direction> {
         })
         .collect()
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_1::Day1;
-    use common::Solution;
-
-    const CASE_A: &str = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82
-";
-    const CASE_B: &str = "L1000";
-    const CASE_C: &str = "L950";
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day1.part_one(CASE_A), 3u16.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day1.part_two(CASE_A), 6u16.into());
-        assert_eq!(Day1.part_two(CASE_B), 10u16.into());
-        assert_eq!(Day1.part_two(CASE_C), 10u16.into());
-    }
-}
@@ -1,8 +1,6 @@
+use common::intern::Graph;
+use common::memo::Memo;
 use common::{Answer, Solution};
-use std::collections::{HashMap, HashSet};
-
-type Graph = HashMap<String, Vec<String>>;
-type Memo = HashMap<(String, State), usize>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum State {
@@ -41,179 +39,108 @@ impl Solution for Day11 {
 
     fn part_one(&self, input: &str) -> Answer {
         let graph = parse(input);
-        let mut memo = HashMap::new();
-        let mut visiting = HashSet::new();
+        let out = graph.id("out").unwrap();
 
-        count_paths("you", &graph, &mut memo, &mut visiting).into()
+        count_paths(graph.id("you").unwrap(), out, &graph, &mut Memo::new()).into()
     }
 
     fn part_two(&self, input: &str) -> Answer {
         let graph = parse(input);
-
-        let mut visited = HashSet::new();
-        let mut memo = HashMap::new();
-
-        dfs("svr", &graph, &mut visited, State::Neither, &mut memo).into()
+        let devices = Devices {
+            out: graph.id("out").unwrap(),
+            dac: graph.id("dac").unwrap(),
+            fft: graph.id("fft").unwrap(),
+        };
+
+        dfs(
+            graph.id("svr").unwrap(),
+            &graph,
+            &devices,
+            State::Neither,
+            &mut Memo::new(),
+        )
+        .into()
     }
 }
 
-fn count_paths(
-    node: &str,
-    graph: &Graph,
-    memo: &mut HashMap<String, usize>,
-    visiting: &mut HashSet<String>,
-) -> usize {
-    if node == "out" {
-        return 1;
-    }
-
-    if let Some(&cached) = memo.get(node) {
-        return cached;
-    }
+struct Devices {
+    out: u32,
+    dac: u32,
+    fft: u32,
+}
 
-    if !visiting.insert(node.to_string()) {
-        return 0;
+// Paths that revisit a node are not counted
+fn count_paths(node: u32, out: u32, graph: &Graph, memo: &mut Memo<u32, usize>) -> usize {
+    if node == out {
+        return 1;
     }
 
-    let paths = graph[node]
-        .iter()
-        .map(|neighbor| count_paths(neighbor, graph, memo, visiting))
-        .sum();
-
-    visiting.remove(node);
-    memo.insert(node.to_string(), paths);
-
-    paths
+    memo.get_or(node, 0, |memo| {
+        graph
+            .neighbors(node)
+            .iter()
+            .map(|&neighbor| count_paths(neighbor, out, graph, memo))
+            .sum()
+    })
 }
 
 fn dfs(
-    node: &str,
+    node: u32,
     graph: &Graph,
-    visited: &mut HashSet<String>,
+    devices: &Devices,
     state: State,
-    memo: &mut Memo,
+    memo: &mut Memo<(u32, State), usize>,
 ) -> usize {
-    let state = match node {
-        "dac" => state.visit_dac(),
-        "fft" => state.visit_fft(),
-        _ => state,
+    let state = if node == devices.dac {
+        state.visit_dac()
+    } else if node == devices.fft {
+        state.visit_fft()
+    } else {
+        state
     };
 
-    if node == "out" {
+    if node == devices.out {
         return if state.is_complete() { 1 } else { 0 };
     }
 
-    let key = (node.to_string(), state);
-    if let Some(&cached) = memo.get(&key) {
-        return cached;
-    }
-
-    if !visited.insert(node.to_string()) {
-        return 0;
-    }
-
-    let total = graph
-        .get(node)
-        .into_iter()
-        .flat_map(|neighbor| neighbor.iter())
-        .fold(0, |acc, next| acc + dfs(next, graph, visited, state, memo));
-
-    visited.remove(node);
-    memo.insert(key, total);
-
-    total
+    memo.get_or((node, state), 0, |memo| {
+        graph
+            .neighbors(node)
+            .iter()
+            .map(|&next| dfs(next, graph, devices, state, memo))
+            .sum()
+    })
 }
 
-fn parse(input: &str) -> Graph {
-    let mut graph = Graph::new();
-
-    input.lines().for_each(|line| {
+fn parse(input: &str) -> Graph<'_> {
+    Graph::from_adjacency(input.lines().map(|line| {
         let (node, parts) = line.split_once(": ").unwrap();
-
-        let neighbors = parts
-            .split_whitespace()
-            .map(|part| part.to_string())
-            .collect::<Vec<_>>();
-
-        graph.insert(node.to_string(), neighbors);
-    });
-    graph
+        (node, parts.split_whitespace())
+    }))
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day_11::{parse, Day11};
-    use common::Solution;
-    use std::collections::HashMap;
-
-    const CASE_A: &str = "aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out
-";
-
-    const CASE_B: &str = "svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out
-";
+    use crate::day_11::parse;
 
-    #[test]
-    fn test_parse() {
-        let mut expected = HashMap::new();
-
-        expected.insert(
-            "aaa".to_string(),
-            vec!["you".to_string(), "hhh".to_string()],
-        );
-        expected.insert(
-            "you".to_string(),
-            vec!["bbb".to_string(), "ccc".to_string()],
-        );
-        expected.insert(
-            "bbb".to_string(),
-            vec!["ddd".to_string(), "eee".to_string()],
-        );
-        expected.insert(
-            "ccc".to_string(),
-            vec!["ddd".to_string(), "eee".to_string(), "fff".to_string()],
-        );
-        expected.insert("ddd".to_string(), vec!["ggg".to_string()]);
-        expected.insert("eee".to_string(), vec!["out".to_string()]);
-        expected.insert("fff".to_string(), vec!["out".to_string()]);
-        expected.insert("ggg".to_string(), vec!["out".to_string()]);
-        expected.insert(
-            "hhh".to_string(),
-            vec!["ccc".to_string(), "fff".to_string(), "iii".to_string()],
-        );
-        expected.insert("iii".to_string(), vec!["out".to_string()]);
-
-        let got = parse(CASE_A);
-        assert_eq!(got, expected);
-    }
+    const CASE_A: &str = include_str!("../../data/2025/examples/11-1.txt");
 
     #[test]
-    fn test_part_one() {
-        assert_eq!(Day11.part_one(CASE_A), 5usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day11.part_two(CASE_B), 2usize.into())
+    fn test_parse() {
+        let graph = parse(CASE_A);
+        assert_eq!(graph.len(), 11);
+
+        let neighbors = |label: &str| {
+            graph
+                .neighbors(graph.id(label).unwrap())
+                .iter()
+                .map(|&id| graph.label(id))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(neighbors("aaa"), vec!["you", "hhh"]);
+        assert_eq!(neighbors("ccc"), vec!["ddd", "eee", "fff"]);
+        assert_eq!(neighbors("hhh"), vec!["ccc", "fff", "iii"]);
+        assert_eq!(neighbors("iii"), vec!["out"]);
+        assert!(neighbors("out").is_empty());
     }
 }
@@ -1,4 +1,6 @@
+use common::ints::ints;
 use common::{Answer, Solution};
+use itertools::Itertools;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 #[derive(Debug, PartialEq)]
@@ -59,32 +61,17 @@ impl Solution for Day2 {
 }
 
 fn parse(input: &str) -> Vec<Range> {
-    input
-        .lines()
-        .map(str::trim)
-        .flat_map(|line| {
-            line.split(',')
-                .map(str::trim)
-                .filter(|s| !s.is_empty())
-                .map(|s| {
-                    let mut parts = s.split('-');
-                    let start = parts.next().unwrap().parse::<usize>().unwrap();
-                    let end = parts.next().unwrap().parse::<usize>().unwrap();
-                    Range { start, end }
-                })
-                .collect::<Vec<Range>>()
-        })
+    ints(input)
+        .tuples()
+        .map(|(start, end)| Range { start, end })
         .collect()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day_2::{parse, Day2, Range};
-    use common::Solution;
+    use crate::day_2::{parse, Range};
 
-    const CASE_A: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
-1698522-1698528,446443-446449,38593856-38593862,565653-565659,
-824824821-824824827,2121212118-2121212124";
+    const CASE_A: &str = include_str!("../../data/2025/examples/02-1.txt");
 
     #[test]
     fn test_parse() {
@@ -135,14 +122,4 @@ mod test {
         assert_eq!(got, expected);
         assert_eq!(got[0], Range { start: 11, end: 22 });
     }
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day2.part_one(CASE_A), 1227775554usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day2.part_two(CASE_A), 4174379265usize.into())
-    }
 }
@@ -78,25 +78,3 @@ fn parse(input: &str) -> Vec<Vec<u32>> {
         .map(|line| line.chars().filter_map(|c| c.to_digit(10)).collect())
         .collect::<Vec<_>>()
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_3::Day3;
-    use common::Solution;
-
-    const CASE_A: &str = "987654321111111
-811111111111119
-234234234234278
-818181911112111
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day3.part_one(CASE_A), 357u64.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day3.part_two(CASE_A), 3121910778619u64.into())
-    }
-}
@@ -1,23 +1,8 @@
+use common::automaton::Automaton;
+use common::bits::BitGrid;
+#[cfg(feature = "viz")]
+use common::viz::{Animate, Canvas, Recording, Rgb};
 use common::{Answer, Solution};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::cmp::PartialEq;
-use std::collections::HashSet;
-
-#[derive(PartialEq, Debug)]
-enum GridItem {
-    Empty,
-    Roll,
-}
-
-impl GridItem {
-    fn from_char(c: char) -> GridItem {
-        match c {
-            '.' => GridItem::Empty,
-            '@' => GridItem::Roll,
-            _ => unreachable!(),
-        }
-    }
-}
 
 pub struct Day4;
 
@@ -26,100 +11,87 @@ impl Solution for Day4 {
         "Day 4".into()
     }
     fn part_one(&self, input: &str) -> Answer {
-        let rolls = parse(input);
-
-        rolls
-            .iter()
-            .filter(|&&(row, col)| count_adjacent(&rolls, row, col) < 4)
-            .count()
-            .into()
+        forklift(parse(input)).step().into()
     }
 
     fn part_two(&self, input: &str) -> Answer {
-        let rolls = parse(input);
+        forklift(parse(input)).run_until_stable().into()
+    }
+}
 
-        (0..)
-            .scan(rolls, |rolls, _| {
-                let accessible: HashSet<_> = rolls
-                    .par_iter()
-                    .filter(|&&(row, col)| count_adjacent(rolls, row, col) < 4)
-                    .copied()
-                    .collect();
+#[cfg(feature = "viz")]
+impl Animate for Day4 {
+    /// One frame per round of removals, with the rolls just taken away in red and
+    /// earlier ones in dark grey
+    fn animate(&self, input: &str, recording: &mut Recording) {
+        let start = parse(input);
+        let mut forklift = forklift(start.clone());
 
-                if accessible.is_empty() {
-                    None
+        let draw = |previous: &BitGrid, current: &BitGrid| {
+            let mut canvas = Canvas::new(start.width(), start.height(), Rgb(15, 15, 25));
+            for (x, y) in start.iter() {
+                let colour = if current.get(x, y) {
+                    Rgb::WHITE
+                } else if previous.get(x, y) {
+                    Rgb::RED
                 } else {
-                    let count = accessible.len();
-                    *rolls = &*rolls - &accessible;
-                    Some(count)
-                }
-            })
-            .sum::<usize>()
-            .into()
+                    Rgb(60, 40, 45)
+                };
+                canvas.set(x, y, colour);
+            }
+            canvas
+        };
+
+        recording.push(draw(&start, &start));
+        loop {
+            let previous = forklift.grid().clone();
+            if forklift.step() == 0 {
+                break;
+            }
+            recording.push(draw(&previous, forklift.grid()));
+        }
+        recording.push(draw(forklift.grid(), forklift.grid()));
     }
 }
 
-fn count_adjacent(grid: &HashSet<(usize, usize)>, row: usize, col: usize) -> usize {
-    let directions = [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ];
-
-    directions
-        .iter()
-        .filter_map(|(dr, dc)| {
-            let new_row = row.checked_add_signed(*dr)?;
-            let new_col = col.checked_add_signed(*dc)?;
-            Some((new_row, new_col))
-        })
-        .filter(|pos| grid.contains(pos))
-        .count()
+// Each generation removes every roll with fewer than four rolls around it
+fn forklift(rolls: BitGrid) -> Automaton<impl Fn(bool, usize) -> bool> {
+    Automaton::new(rolls, |roll, neighbours| roll && neighbours >= 4)
 }
 
-fn parse(input: &str) -> HashSet<(usize, usize)> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(col, c)| (col, GridItem::from_char(c)))
-                .filter(|(_, c)| *c == GridItem::Roll)
-                .map(move |(col, _)| (row, col))
-        })
-        .collect()
+fn parse(input: &str) -> BitGrid {
+    BitGrid::parse(input, |c| match c {
+        '.' => false,
+        '@' => true,
+        _ => unreachable!(),
+    })
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "viz"))]
 mod test {
     use crate::day_4::Day4;
-    use common::Solution;
+    use common::viz::{Animate, Recording, Rgb};
 
-    const CASE_A: &str = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.
-";
+    const CASE_A: &str = include_str!("../../data/2025/examples/04-1.txt");
 
     #[test]
-    fn test_part_one() {
-        assert_eq!(Day4.part_one(CASE_A), 13usize.into())
-    }
+    fn test_animate() {
+        let mut recording = Recording::new();
+        Day4.animate(CASE_A, &mut recording);
 
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day4.part_two(CASE_A), 43usize.into())
+        let removed = |frame: usize| {
+            let canvas = &recording.frames()[frame];
+            (0..canvas.height())
+                .flat_map(|y| (0..canvas.width()).map(move |x| (x, y)))
+                .filter(|&(x, y)| canvas.get(x, y) == Some(Rgb::RED))
+                .count()
+        };
+
+        // The starting grid, the nine rounds of removals and the final grid
+        assert_eq!(recording.len(), 11);
+        assert_eq!(removed(0), 0);
+        assert_eq!(removed(1), 13);
+        assert_eq!((1..10).map(removed).sum::<usize>(), 43);
+        assert_eq!(removed(10), 0);
     }
 }
@@ -76,32 +76,3 @@ fn parse(input: &str) -> (Vec<Range>, Vec<usize>) {
 
     (fresh, ingredients)
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_5::Day5;
-    use common::Solution;
-
-    const CASE_A: &str = "3-5
-10-14
-16-20
-12-18
-
-1
-5
-8
-11
-17
-32
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day5.part_one(CASE_A), 3usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day5.part_two(CASE_A), 14usize.into())
-    }
-}
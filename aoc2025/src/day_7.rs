@@ -1,6 +1,9 @@
+use common::bits::BitSet;
+#[cfg(feature = "viz")]
+use common::viz::{Animate, Canvas, Recording, Rgb, Visualize};
 use common::{Answer, Solution};
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 #[derive(PartialEq, Debug)]
 enum Manifold {
@@ -35,55 +38,154 @@ impl Solution for Day7 {
 
         items[starting_row + 1..]
             .iter()
-            .fold((HashSet::from([starting_col]), 0), |(cols, total), line| {
-                let (next_cols, splits) = process_row(line, cols);
-                (next_cols, total + splits)
-            })
+            .fold(
+                (BitSet::from_iter([starting_col]), 0),
+                |(cols, total), line| {
+                    let (next_cols, splits) = process_row(line, cols);
+                    (next_cols, total + splits)
+                },
+            )
             .1
             .into()
     }
 
     fn part_two(&self, input: &str) -> Answer {
+        timelines(&parse(input), None).into()
+    }
+}
+
+#[cfg(feature = "viz")]
+impl Visualize for Day7 {
+    /// Beams brighten with the number of timelines passing through them, on a log
+    /// scale. Splitters a beam reached are red, the rest grey
+    fn visualize(&self, input: &str) -> Canvas {
         let grid = parse(input);
+        let beams = Beams::new(&grid);
+        beams.draw(&grid, beams.rows.len())
+    }
+}
 
-        let (starting_row, starting_col) = find_starting_position(&grid);
+#[cfg(feature = "viz")]
+impl Animate for Day7 {
+    /// The beams reaching one row further down each frame
+    fn animate(&self, input: &str, recording: &mut Recording) {
+        let grid = parse(input);
+        let beams = Beams::new(&grid);
+        for rows in 1..=beams.rows.len() {
+            recording.push(beams.draw(&grid, rows));
+        }
+    }
+}
 
-        grid[starting_row + 1..]
+// Number of timelines in each column, row by row from the start
+#[cfg(feature = "viz")]
+struct Beams {
+    starting_row: usize,
+    // rows[i] holds the beams leaving row starting_row + i, which are also the ones
+    // drawn on it, so a splitter is reached when the row above had a beam there
+    rows: Vec<HashMap<usize, usize>>,
+}
+
+#[cfg(feature = "viz")]
+impl Beams {
+    fn new(grid: &[Vec<Manifold>]) -> Self {
+        let (starting_row, _) = find_starting_position(grid);
+        let mut rows = Vec::new();
+        timelines(grid, Some(&mut rows));
+
+        Beams { starting_row, rows }
+    }
+
+    // The grid with only the first `shown` rows of beams
+    fn draw(&self, grid: &[Vec<Manifold>], shown: usize) -> Canvas {
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let mut canvas = Canvas::new(width, grid.len(), Rgb(15, 20, 35));
+
+        // Scaled against every row, so brightness stays put as more rows appear
+        let most = self
+            .rows
             .iter()
-            .fold(
-                HashMap::from([(starting_col, 1)]),
-                |position_counts, line| {
-                    let width = line.len();
-
-                    position_counts
-                        .into_iter()
-                        .filter(|&(col, _)| col < width)
-                        .flat_map(|(col, count)| match line[col] {
-                            Manifold::Splitter => vec![(col - 1, count), (col + 1, count)],
-                            _ => vec![(col, count)],
-                        })
-                        .into_group_map()
-                        .into_iter()
-                        .map(|(col, counts)| (col, counts.into_iter().sum()))
-                        .collect()
-                },
-            )
-            .values()
-            .sum::<usize>()
-            .into()
+            .flat_map(|r| r.values())
+            .max()
+            .copied()
+            .unwrap_or(1);
+        let brightness = |count: usize| (count as f64).ln_1p() / (most as f64).ln_1p();
+
+        for (y, line) in grid.iter().enumerate() {
+            let i = y.wrapping_sub(self.starting_row);
+            let beams = self.rows[..shown].get(i);
+            let reached = i
+                .checked_sub(1)
+                .and_then(|above| self.rows[..shown].get(above));
+
+            for (x, cell) in line.iter().enumerate() {
+                let colour = match cell {
+                    Manifold::Start => Rgb::WHITE,
+                    Manifold::Splitter if reached.is_some_and(|r| r.contains_key(&x)) => Rgb::RED,
+                    Manifold::Splitter => Rgb::GREY,
+                    _ => match beams.and_then(|b| b.get(&x)) {
+                        Some(&count) => Rgb(40, 60, 120).mix(Rgb(120, 230, 255), brightness(count)),
+                        None => continue,
+                    },
+                };
+                canvas.set(x, y, colour);
+            }
+        }
+
+        canvas
+    }
+}
+
+/// Number of timelines that reach the bottom. `record` is given the timelines in each
+/// column leaving every row from the start down
+fn timelines(grid: &[Vec<Manifold>], mut record: Option<&mut Vec<HashMap<usize, usize>>>) -> usize {
+    let (starting_row, starting_col) = find_starting_position(grid);
+
+    let mut position_counts = HashMap::from([(starting_col, 1)]);
+    for line in &grid[starting_row + 1..] {
+        let next = split_timelines(line, &position_counts);
+        if let Some(rows) = record.as_deref_mut() {
+            rows.push(position_counts);
+        }
+        position_counts = next;
     }
+
+    let total = position_counts.values().sum();
+    if let Some(rows) = record {
+        rows.push(position_counts);
+    }
+    total
 }
 
-fn process_row(line: &[Manifold], active_columns: HashSet<usize>) -> (HashSet<usize>, usize) {
+fn split_timelines(
+    line: &[Manifold],
+    position_counts: &HashMap<usize, usize>,
+) -> HashMap<usize, usize> {
+    let width = line.len();
+
+    position_counts
+        .iter()
+        .filter(|&(&col, _)| col < width)
+        .flat_map(|(&col, &count)| match line[col] {
+            Manifold::Splitter => vec![(col - 1, count), (col + 1, count)],
+            _ => vec![(col, count)],
+        })
+        .into_group_map()
+        .into_iter()
+        .map(|(col, counts)| (col, counts.into_iter().sum()))
+        .collect()
+}
+
+fn process_row(line: &[Manifold], active_columns: BitSet) -> (BitSet, usize) {
     let width = line.len();
 
     let splits_count = active_columns
         .iter()
-        .filter(|&&col| col < width && line[col] == Manifold::Splitter)
+        .filter(|&col| col < width && line[col] == Manifold::Splitter)
         .count();
 
     let next_columns = active_columns
-        .into_iter()
+        .iter()
         .filter(|&col| col < width)
         .flat_map(|col| match line[col] {
             Manifold::Splitter => vec![col - 1, col + 1],
@@ -111,37 +213,3 @@ fn parse(input: &str) -> Vec<Vec<Manifold>> {
         .map(|line| line.chars().map(Manifold::from_char).collect())
         .collect()
 }
-
-#[cfg(test)]
-mod test {
-    use crate::day_7::Day7;
-    use common::Solution;
-
-    const CASE_A: &str = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............
-";
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day7.part_one(CASE_A), 21usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day7.part_two(CASE_A), 40usize.into())
-    }
-}
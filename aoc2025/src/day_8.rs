@@ -1,3 +1,6 @@
+use common::ints::ints_per_line;
+#[cfg(feature = "viz")]
+use common::viz::{palette, Canvas, Rgb, Viewport, Visualize};
 use common::{Answer, Solution};
 use itertools::Itertools;
 use std::collections::HashMap;
@@ -78,13 +81,8 @@ impl Solution for Day8 {
 
         let mut circuit = Circuit::new(grid.len());
 
-        // Determine number of connections based on input size
-        // Example has 20 points -> 10 connections
-        // Full input likely has many more -> 1000 connections
-        let num_connections = if grid.len() == 20 { 10 } else { 1000 };
-
         // Process the shortest pairs
-        for &(i, j, _dist) in pairwise_distance.iter().take(num_connections) {
+        for &(i, j, _dist) in pairwise_distance.iter().take(connection_count(&grid)) {
             circuit.get_union(i, j);
         }
 
@@ -117,6 +115,66 @@ impl Solution for Day8 {
     }
 }
 
+#[cfg(feature = "viz")]
+impl Visualize for Day8 {
+    /// Junction boxes seen from above, with the connections part one makes. The three
+    /// largest circuits get their own colours and everything else is grey
+    fn visualize(&self, input: &str) -> Canvas {
+        let grid = parse(input);
+        let mut pairwise_distance = calculate_pairwise_distances(&grid);
+        pairwise_distance.sort_by_key(|e| e.2);
+
+        let connections =
+            &pairwise_distance[..connection_count(&grid).min(pairwise_distance.len())];
+        let mut circuit = Circuit::new(grid.len());
+        for &(i, j, _dist) in connections {
+            circuit.get_union(i, j);
+        }
+
+        let roots = (0..grid.len()).map(|i| circuit.find(i)).collect_vec();
+        let largest = roots
+            .iter()
+            .unique()
+            .sorted_by_key(|&&root| std::cmp::Reverse(circuit.size[root]))
+            .take(3)
+            .collect_vec();
+        let colour = |i: usize| {
+            largest
+                .iter()
+                .position(|&&root| root == roots[i])
+                .map_or(Rgb::GREY, palette)
+        };
+
+        let view = Viewport::fit(grid.iter().map(|p| (p.x, p.y)), 240);
+        let mut canvas = view.canvas(Rgb(15, 15, 25));
+
+        for &(i, j, _dist) in connections {
+            let dimmed = colour(i).mix(Rgb(15, 15, 25), 0.5);
+            canvas.line(
+                view.pixel((grid[i].x, grid[i].y)),
+                view.pixel((grid[j].x, grid[j].y)),
+                dimmed,
+            );
+        }
+        for (i, p) in grid.iter().enumerate() {
+            let (x, y) = view.pixel((p.x, p.y));
+            canvas.set(x, y, colour(i));
+        }
+
+        canvas
+    }
+}
+
+// Example has 20 points -> 10 connections
+// Full input likely has many more -> 1000 connections
+fn connection_count(grid: &[Point]) -> usize {
+    if grid.len() == 20 {
+        10
+    } else {
+        1000
+    }
+}
+
 fn calculate_pairwise_distances(grid: &[Point]) -> Vec<(usize, usize, i64)> {
     grid.iter()
         .enumerate()
@@ -130,46 +188,16 @@ fn calculate_pairwise_distances(grid: &[Point]) -> Vec<(usize, usize, i64)> {
 }
 
 fn parse(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .map(|line| {
-            let parts = line.split(",").collect_vec();
-            assert_eq!(parts.len(), 3);
-            Point {
-                x: parts[0].parse().unwrap(),
-                y: parts[1].parse().unwrap(),
-                z: parts[2].parse().unwrap(),
-            }
-        })
+    ints_per_line(input)
+        .map(|[x, y, z]| Point { x, y, z })
         .collect()
 }
 
 #[cfg(test)]
 mod test {
-    use crate::day_8::{parse, Day8, Point};
-    use common::Solution;
-
-    const CASE_A: &str = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689
-";
+    use crate::day_8::{parse, Point};
+
+    const CASE_A: &str = include_str!("../../data/2025/examples/08-1.txt");
 
     #[test]
     fn test_parse() {
@@ -183,14 +211,4 @@ mod test {
         assert_eq!(got.len(), 20);
         assert_eq!(expected_a, got[0]);
     }
-
-    #[test]
-    fn test_part_one() {
-        assert_eq!(Day8.part_one(CASE_A), 40usize.into())
-    }
-
-    #[test]
-    fn test_part_two() {
-        assert_eq!(Day8.part_two(CASE_A), 25272usize.into())
-    }
 }
@@ -23,3 +23,12 @@ pub const ALL: &[&dyn common::Solution] = &[
     &day_10::Day10,
     &day_11::Day11,
 ];
+
+/// Days that can draw their solution, by day number
+#[cfg(feature = "viz")]
+pub const VISUALIZATIONS: &[(u32, &dyn common::viz::Visualize)] =
+    &[(7, &day_7::Day7), (8, &day_8::Day8), (9, &day_9::Day9)];
+
+/// Days that can replay their simulation, by day number
+#[cfg(feature = "viz")]
+pub const ANIMATIONS: &[(u32, &dyn common::viz::Animate)] = &[(4, &day_4::Day4), (7, &day_7::Day7)];
@@ -0,0 +1,82 @@
+use crate::Answer;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::{fs, io};
+
+/// Answers the site already accepted, by year and day, read from JSON like
+/// `{"2023": {"1": ["56506", "56017"]}}`. A part not solved yet is `null`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(HashMap<u32, HashMap<u32, [Option<String>; 2]>>);
+
+/// How an answer compares with the stored one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Holds the stored answer
+    Wrong(String),
+    /// Nothing stored for that part
+    Unknown,
+}
+
+impl Answers {
+    /// Answers in `data/answers.json`, or none if there is no such file
+    pub fn load() -> io::Result<Self> {
+        Answers::from_file(Path::new("data/answers.json"))
+    }
+
+    pub fn from_file(file: &Path) -> io::Result<Self> {
+        match fs::read_to_string(file) {
+            Ok(json) => Answers::parse(&json),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(json: &str) -> io::Result<Self> {
+        serde_json::from_str(json).map_err(io::Error::other)
+    }
+
+    /// Stored answer for `part` 1 or 2 of the day
+    pub fn get(&self, year: u32, day: u32, part: u32) -> Option<&str> {
+        let parts = self.0.get(&year)?.get(&day)?;
+        parts.get(part.checked_sub(1)? as usize)?.as_deref()
+    }
+
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &Answer) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer.to_string() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.to_string()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::answers::{Answers, Verdict};
+    use crate::Answer;
+
+    #[test]
+    fn test_answers() {
+        let answers = Answers::parse(r#"{"2023": {"1": ["142", null]}}"#).unwrap();
+
+        assert_eq!(answers.get(2023, 1, 1), Some("142"));
+        assert_eq!(answers.get(2023, 1, 2), None);
+        assert_eq!(answers.get(2023, 1, 3), None);
+        assert_eq!(answers.get(2024, 1, 1), None);
+
+        assert_eq!(
+            answers.check(2023, 1, 1, &Answer::U32(142)),
+            Verdict::Correct
+        );
+        assert_eq!(
+            answers.check(2023, 1, 1, &Answer::I64(-142)),
+            Verdict::Wrong("142".into())
+        );
+        assert_eq!(answers.check(2023, 1, 2, &Answer::U32(1)), Verdict::Unknown);
+
+        assert!(Answers::parse(r#"{"2023": {"1": [142]}}"#).is_err());
+    }
+}
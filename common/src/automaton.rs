@@ -0,0 +1,132 @@
+use crate::bits::{BitGrid, BitSet};
+
+/// Steps a grid of on/off cells where each cell's next state depends on its own
+/// state and how many of its eight neighbours are on, e.g. Conway's Life is
+/// `|alive, n| n == 3 || (alive && n == 2)`.
+///
+/// Only cells next to something that changed in the previous generation can change
+/// in the next, so those are the only ones evaluated after the first step.
+/// Neighbour counts are kept up to date as cells flip rather than recounted.
+pub struct Automaton<R> {
+    grid: BitGrid,
+    rule: R,
+    counts: Vec<u8>,
+    // Cells to evaluate in the next step, as row-major indices
+    dirty: BitSet,
+    changes: Vec<usize>,
+}
+
+impl<R: Fn(bool, usize) -> bool> Automaton<R> {
+    pub fn new(grid: BitGrid, rule: R) -> Self {
+        let counts = grid.neighbour_counts();
+        let dirty = (0..grid.width() * grid.height()).collect();
+
+        Automaton {
+            grid,
+            rule,
+            counts,
+            dirty,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Advances one generation, returning how many cells changed
+    pub fn step(&mut self) -> usize {
+        let width = self.grid.width();
+
+        // Decide every cell before flipping any, so the update is simultaneous
+        let flips = self
+            .dirty
+            .iter()
+            .filter(|&i| {
+                let alive = self.grid.get(i % width, i / width);
+                (self.rule)(alive, self.counts[i] as usize) != alive
+            })
+            .collect::<Vec<_>>();
+
+        self.dirty.clear();
+        for &i in &flips {
+            let (x, y) = (i % width, i / width);
+            let alive = !self.grid.get(x, y);
+            self.grid.set(x, y, alive);
+            self.dirty.insert(i);
+
+            for (nx, ny) in self.grid.neighbours(x, y) {
+                let n = ny * width + nx;
+                if alive {
+                    self.counts[n] += 1;
+                } else {
+                    self.counts[n] -= 1;
+                }
+                self.dirty.insert(n);
+            }
+        }
+
+        self.changes.push(flips.len());
+        flips.len()
+    }
+
+    /// Advances `steps` generations, returning how many cells changed in total
+    pub fn run(&mut self, steps: usize) -> usize {
+        (0..steps).map(|_| self.step()).sum()
+    }
+
+    /// Steps until a generation changes nothing, returning how many cells changed in
+    /// total. Never returns if the grid oscillates
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut total = 0;
+        loop {
+            match self.step() {
+                0 => return total,
+                changed => total += changed,
+            }
+        }
+    }
+
+    pub fn grid(&self) -> &BitGrid {
+        &self.grid
+    }
+
+    /// Number of steps taken so far
+    pub fn generation(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// How many cells changed in each generation so far
+    pub fn changes(&self) -> &[usize] {
+        &self.changes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::automaton::Automaton;
+    use crate::bits::BitGrid;
+
+    #[test]
+    fn test_life() {
+        let blinker = BitGrid::parse(".....\n..#..\n..#..\n..#..\n.....", |c| c == '#');
+        let mut life = Automaton::new(blinker.clone(), |alive, n| n == 3 || (alive && n == 2));
+
+        assert_eq!(life.step(), 4);
+        assert_eq!(
+            life.grid().iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 2), (3, 2)]
+        );
+        assert_eq!(life.run(3), 12);
+        assert_eq!(life.grid(), &blinker);
+        assert_eq!(life.changes(), &[4, 4, 4, 4]);
+        assert_eq!(life.generation(), 4);
+    }
+
+    #[test]
+    fn test_until_stable() {
+        // Cells with fewer than two neighbours die off, layer by layer
+        let line = BitGrid::parse("#####", |c| c == '#');
+        let mut erosion = Automaton::new(line, |alive, n| alive && n >= 2);
+
+        assert_eq!(erosion.run_until_stable(), 5);
+        assert!(erosion.grid().is_empty());
+        assert_eq!(erosion.changes(), &[2, 2, 1, 0]);
+    }
+}
@@ -0,0 +1,409 @@
+use std::hash::{Hash, Hasher};
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Set of small non-negative integers, one bit each. Grows as needed on insert
+#[derive(Debug, Default, Clone)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        BitSet::default()
+    }
+
+    /// Empty set with room for `0..capacity` without growing
+    pub fn with_capacity(capacity: usize) -> Self {
+        BitSet {
+            words: vec![0; capacity.div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// Adds `i`, returning whether it was new
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / WORD_BITS, 1 << (i % WORD_BITS));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        new
+    }
+
+    /// Removes `i`, returning whether it was there
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / WORD_BITS, 1 << (i % WORD_BITS));
+        match self.words.get_mut(word) {
+            Some(w) if *w & bit != 0 => {
+                *w &= !bit;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.words
+            .get(i / WORD_BITS)
+            .is_some_and(|w| w & (1 << (i % WORD_BITS)) != 0)
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Elements in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    i * WORD_BITS + bit
+                })
+            })
+        })
+    }
+
+    pub fn union_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a & b);
+    }
+
+    pub fn difference_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a & !b);
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &BitSet) {
+        self.combine(other, |a, b| a ^ b);
+    }
+
+    pub fn is_subset(&self, other: &BitSet) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, &w)| w & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+
+    // The words up to the last one with an element in it, so that sets holding the
+    // same elements compare and hash the same however much room they have
+    fn used_words(&self) -> &[u64] {
+        let used = self
+            .words
+            .iter()
+            .rposition(|&w| w != 0)
+            .map_or(0, |i| i + 1);
+        &self.words[..used]
+    }
+
+    // Applies `op` word by word, treating missing words as empty
+    fn combine(&mut self, other: &BitSet, op: impl Fn(u64, u64) -> u64) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (i, word) in self.words.iter_mut().enumerate() {
+            *word = op(*word, other.words.get(i).copied().unwrap_or(0));
+        }
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &BitSet) -> bool {
+        self.used_words() == other.used_words()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.used_words().hash(state);
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        iter.into_iter().for_each(|i| {
+            set.insert(i);
+        });
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|i| {
+            self.insert(i);
+        });
+    }
+}
+
+macro_rules! impl_set_op {
+    ($($trait:ident $method:ident $with:ident),*) => {
+        $(impl $trait for &BitSet {
+            type Output = BitSet;
+
+            fn $method(self, rhs: &BitSet) -> BitSet {
+                let mut result = self.clone();
+                result.$with(rhs);
+                result
+            }
+        })*
+    };
+}
+
+impl_set_op!(
+    BitOr bitor union_with,
+    BitAnd bitand intersect_with,
+    Sub sub difference_with,
+    BitXor bitxor symmetric_difference_with
+);
+
+/// Fixed size grid of on/off cells, stored as a [`BitSet`] in row-major order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    cells: BitSet,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        BitGrid {
+            width,
+            height,
+            cells: BitSet::with_capacity(width * height),
+        }
+    }
+
+    /// Grid from lines of text, with the cells where `is_set` holds turned on. The
+    /// width is that of the longest line
+    pub fn parse(input: &str, is_set: impl Fn(char) -> bool) -> Self {
+        let width = input.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let height = input.lines().count();
+
+        let mut grid = BitGrid::new(width, height);
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if is_set(c) {
+                    grid.set(x, y, true);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is on. Anything outside the grid is off
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells.contains(y * self.width + x)
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        if value {
+            self.cells.insert(y * self.width + x);
+        } else {
+            self.cells.remove(y * self.width + x);
+        }
+    }
+
+    /// Number of cells that are on
+    pub fn count(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// `(x, y)` of every cell that is on, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells.iter().map(|i| (i % self.width, i / self.width))
+    }
+
+    /// The up to eight cells around `(x, y)` that lie inside the grid
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&d| d != (0, 0))
+            .filter_map(move |(dx, dy)| {
+                let nx = x.checked_add_signed(dx).filter(|&nx| nx < width)?;
+                let ny = y.checked_add_signed(dy).filter(|&ny| ny < height)?;
+                Some((nx, ny))
+            })
+    }
+
+    /// Number of the eight surrounding cells that are on
+    pub fn neighbour_count(&self, x: usize, y: usize) -> usize {
+        self.neighbours(x, y)
+            .filter(|&(nx, ny)| self.get(nx, ny))
+            .count()
+    }
+
+    /// Number of surrounding cells that are on, for every cell at once, in row-major
+    /// order. Each cell that is on adds one to its neighbours, which is much cheaper
+    /// than asking every cell when the grid is sparse
+    pub fn neighbour_counts(&self) -> Vec<u8> {
+        let mut counts = vec![0; self.width * self.height];
+        for (x, y) in self.iter() {
+            for (nx, ny) in self.neighbours(x, y) {
+                counts[ny * self.width + nx] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Grid with the cells where `f(x, y)` holds turned on
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> bool) -> Self {
+        let mut grid = BitGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if f(x, y) {
+                    grid.set(x, y, true);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.check_size(other);
+        self.cells.union_with(&other.cells);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.check_size(other);
+        self.cells.intersect_with(&other.cells);
+    }
+
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.check_size(other);
+        self.cells.difference_with(&other.cells);
+    }
+
+    fn check_size(&self, other: &BitGrid) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids have different sizes"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bits::{BitGrid, BitSet};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    #[test]
+    fn test_bit_set() {
+        let mut set = BitSet::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(130));
+        assert!(set.contains(130));
+        assert!(!set.contains(1000));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 130]);
+
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert!(!set.remove(1000));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = [1, 2, 3, 100].into_iter().collect::<BitSet>();
+        let b = [3, 4, 200].into_iter().collect::<BitSet>();
+
+        let collect = |s: BitSet| s.iter().collect::<Vec<_>>();
+        assert_eq!(collect(&a | &b), vec![1, 2, 3, 4, 100, 200]);
+        assert_eq!(collect(&a & &b), vec![3]);
+        assert_eq!(collect(&a - &b), vec![1, 2, 100]);
+        assert_eq!(collect(&a ^ &b), vec![1, 2, 4, 100, 200]);
+        assert!((&a & &b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+    }
+
+    #[test]
+    fn test_equality_ignores_capacity() {
+        let hash = |s: &BitSet| {
+            let mut hasher = DefaultHasher::new();
+            s.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(BitSet::new(), BitSet::with_capacity(128));
+
+        let mut removed = [5].into_iter().collect::<BitSet>();
+        removed.insert(100);
+        removed.remove(100);
+        let small = [5].into_iter().collect::<BitSet>();
+        assert_eq!(removed, small);
+        assert_eq!(hash(&removed), hash(&small));
+
+        let combined = &small & &[5, 300].into_iter().collect::<BitSet>();
+        assert_eq!(combined, small);
+        assert_eq!(hash(&combined), hash(&small));
+        assert_ne!(combined, BitSet::new());
+    }
+
+    #[test]
+    fn test_bit_grid() {
+        let grid = BitGrid::parse("@.@\n.@.\n@@.", |c| c == '@');
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.count(), 5);
+        assert!(grid.get(1, 1));
+        assert!(!grid.get(3, 0));
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (1, 1), (0, 2), (1, 2)]
+        );
+
+        assert_eq!(grid.neighbour_count(1, 1), 4);
+        assert_eq!(grid.neighbour_count(0, 0), 1);
+        assert_eq!(grid.neighbours(0, 0).count(), 3);
+
+        let counts = grid.neighbour_counts();
+        for y in 0..3 {
+            for x in 0..3 {
+                assert_eq!(counts[y * 3 + x] as usize, grid.neighbour_count(x, y));
+            }
+        }
+
+        let mut rest = grid.clone();
+        rest.difference_with(&BitGrid::from_fn(3, 3, |_, y| y == 2));
+        assert_eq!(rest.count(), 3);
+    }
+}
@@ -0,0 +1,256 @@
+use std::ops::RangeInclusive;
+
+/// Dense indices for a sparse set of coordinates along one axis.
+///
+/// Every distinct coordinate gets its own cell of width one. Axes built with
+/// [`Axis::with_gaps`] also get a cell for every run of coordinates between two of
+/// them, whose width is the length of that run, so every coordinate from the
+/// smallest to the largest belongs to exactly one cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Axis {
+    // First coordinate of each cell, ascending
+    starts: Vec<i64>,
+    widths: Vec<i64>,
+}
+
+impl Axis {
+    /// One cell per distinct coordinate, without gap cells
+    pub fn new(coordinates: impl IntoIterator<Item = i64>) -> Self {
+        let sorted = sorted_unique(coordinates);
+        let widths = vec![1; sorted.len()];
+        Axis {
+            starts: sorted,
+            widths,
+        }
+    }
+
+    /// One cell per distinct coordinate plus one per non-empty gap between them
+    pub fn with_gaps(coordinates: impl IntoIterator<Item = i64>) -> Self {
+        let sorted = sorted_unique(coordinates);
+        let mut starts = Vec::with_capacity(sorted.len() * 2);
+        let mut widths = Vec::with_capacity(sorted.len() * 2);
+
+        for (i, &c) in sorted.iter().enumerate() {
+            if i > 0 && c - sorted[i - 1] > 1 {
+                starts.push(sorted[i - 1] + 1);
+                widths.push(c - sorted[i - 1] - 1);
+            }
+            starts.push(c);
+            widths.push(1);
+        }
+
+        Axis { starts, widths }
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Cell that `coordinate` falls in, if any
+    pub fn index(&self, coordinate: i64) -> Option<usize> {
+        let cell = self
+            .starts
+            .partition_point(|&s| s <= coordinate)
+            .checked_sub(1)?;
+        (coordinate < self.starts[cell] + self.widths[cell]).then_some(cell)
+    }
+
+    /// First coordinate covered by `cell`
+    pub fn start(&self, cell: usize) -> i64 {
+        self.starts[cell]
+    }
+
+    /// Number of coordinates covered by `cell`
+    pub fn width(&self, cell: usize) -> i64 {
+        self.widths[cell]
+    }
+
+    /// Coordinates covered by `cell`
+    pub fn range(&self, cell: usize) -> RangeInclusive<i64> {
+        self.starts[cell]..=self.starts[cell] + self.widths[cell] - 1
+    }
+}
+
+fn sorted_unique(coordinates: impl IntoIterator<Item = i64>) -> Vec<i64> {
+    let mut sorted = coordinates.into_iter().collect::<Vec<_>>();
+    sorted.sort_unstable();
+    sorted.dedup();
+    sorted
+}
+
+/// Grid over two compressed axes, where each cell stands for a whole rectangle of
+/// real coordinates
+#[derive(Debug, Clone)]
+pub struct CompressedGrid<T> {
+    x: Axis,
+    y: Axis,
+    cells: Vec<T>,
+}
+
+impl<T> CompressedGrid<T> {
+    /// Fills each cell from its real x and y ranges
+    pub fn from_fn<F>(x: Axis, y: Axis, mut f: F) -> Self
+    where
+        F: FnMut(RangeInclusive<i64>, RangeInclusive<i64>) -> T,
+    {
+        let cells = (0..y.len())
+            .flat_map(|cy| (0..x.len()).map(move |cx| (cx, cy)))
+            .map(|(cx, cy)| f(x.range(cx), y.range(cy)))
+            .collect();
+
+        CompressedGrid { x, y, cells }
+    }
+
+    pub fn new(x: Axis, y: Axis, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let cells = vec![value; x.len() * y.len()];
+        CompressedGrid { x, y, cells }
+    }
+
+    pub fn x_axis(&self) -> &Axis {
+        &self.x
+    }
+
+    pub fn y_axis(&self) -> &Axis {
+        &self.y
+    }
+
+    /// Number of cells across
+    pub fn width(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Number of cells down
+    pub fn height(&self) -> usize {
+        self.y.len()
+    }
+
+    pub fn get(&self, cx: usize, cy: usize) -> &T {
+        &self.cells[cy * self.x.len() + cx]
+    }
+
+    pub fn get_mut(&mut self, cx: usize, cy: usize) -> &mut T {
+        &mut self.cells[cy * self.x.len() + cx]
+    }
+
+    /// Cell containing the real point `(x, y)`
+    pub fn cell(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        Some((self.x.index(x)?, self.y.index(y)?))
+    }
+
+    /// Number of real points a cell stands for
+    pub fn weight(&self, cx: usize, cy: usize) -> i64 {
+        self.x.width(cx) * self.y.width(cy)
+    }
+
+    /// Real x and y ranges of a cell
+    pub fn bounds(&self, cx: usize, cy: usize) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
+        (self.x.range(cx), self.y.range(cy))
+    }
+
+    /// Summed area table of `f` over the cells, for constant time rectangle queries
+    pub fn summed_area<F>(&self, mut f: F) -> SummedArea
+    where
+        F: FnMut(&T) -> i64,
+    {
+        SummedArea::new(self.width(), self.height(), |cx, cy| f(self.get(cx, cy)))
+    }
+
+    /// Like [`CompressedGrid::summed_area`], with each cell's value multiplied by its weight
+    pub fn weighted_summed_area<F>(&self, mut f: F) -> SummedArea
+    where
+        F: FnMut(&T) -> i64,
+    {
+        SummedArea::new(self.width(), self.height(), |cx, cy| {
+            f(self.get(cx, cy)) * self.weight(cx, cy)
+        })
+    }
+}
+
+/// Prefix sums over a grid of cells
+#[derive(Debug, Clone)]
+pub struct SummedArea {
+    width: usize,
+    // table[y * (width + 1) + x] is the sum of every cell above and left of (x, y)
+    table: Vec<i64>,
+}
+
+impl SummedArea {
+    fn new<F>(width: usize, height: usize, mut value: F) -> Self
+    where
+        F: FnMut(usize, usize) -> i64,
+    {
+        let stride = width + 1;
+        let mut table = vec![0; stride * (height + 1)];
+
+        for cy in 0..height {
+            for cx in 0..width {
+                table[(cy + 1) * stride + cx + 1] =
+                    value(cx, cy) + table[cy * stride + cx + 1] + table[(cy + 1) * stride + cx]
+                        - table[cy * stride + cx];
+            }
+        }
+
+        SummedArea { width, table }
+    }
+
+    /// Sum over the cells in `x` by `y`
+    pub fn sum(&self, x: RangeInclusive<usize>, y: RangeInclusive<usize>) -> i64 {
+        let stride = self.width + 1;
+        let (x0, x1) = (*x.start(), *x.end() + 1);
+        let (y0, y1) = (*y.start(), *y.end() + 1);
+
+        self.table[y1 * stride + x1] + self.table[y0 * stride + x0]
+            - self.table[y0 * stride + x1]
+            - self.table[y1 * stride + x0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compress::{Axis, CompressedGrid};
+
+    #[test]
+    fn test_axis() {
+        let axis = Axis::new([30, 10, 20, 10]);
+        assert_eq!(axis.len(), 3);
+        assert_eq!(axis.index(20), Some(1));
+        assert_eq!(axis.index(15), None);
+
+        let axis = Axis::with_gaps([10, 11, 20]);
+        assert_eq!(axis.len(), 4);
+        assert_eq!(axis.index(11), Some(1));
+        assert_eq!(axis.index(15), Some(2));
+        assert_eq!(axis.range(2), 12..=19);
+        assert_eq!(axis.width(2), 8);
+        assert_eq!(axis.index(9), None);
+        assert_eq!(axis.index(21), None);
+    }
+
+    #[test]
+    fn test_grid() {
+        let x = Axis::with_gaps([0, 10]);
+        let y = Axis::with_gaps([0, 5]);
+
+        // Mark everything left of x = 5
+        let grid = CompressedGrid::from_fn(x, y, |xs, _| *xs.start() < 5);
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.cell(4, 4), Some((1, 1)));
+        assert_eq!(grid.weight(1, 1), 9 * 4);
+        assert_eq!(grid.bounds(2, 0), (10..=10, 0..=0));
+
+        let area = grid.weighted_summed_area(|&marked| marked as i64);
+        assert_eq!(area.sum(0..=2, 0..=2), 10 * 6);
+        assert_eq!(area.sum(2..=2, 0..=2), 0);
+
+        let count = grid.summed_area(|&marked| marked as i64);
+        assert_eq!(count.sum(0..=2, 0..=2), 6);
+    }
+}
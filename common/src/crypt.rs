@@ -0,0 +1,131 @@
+//! Puzzle inputs kept in the repository encrypted, as they are not to be redistributed.
+//!
+//! `data/<year>/<dd>.txt.enc` is a day's input sealed with ChaCha20-Poly1305, stored
+//! as the random nonce followed by the ciphertext. The key is 32 bytes written as 64
+//! hex digits, shared by the team and never committed: it is read from the `AOC_KEY`
+//! environment variable, or else from a `.aoc-key` file
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+pub const KEY_VAR: &str = "AOC_KEY";
+pub const KEY_FILE: &str = ".aoc-key";
+
+const NONCE_LEN: usize = 12;
+
+/// Where the encrypted copy of an input goes, `01.txt` becoming `01.txt.enc`
+pub fn encrypted_path(file: &Path) -> PathBuf {
+    let mut name = OsString::from(file);
+    name.push(".enc");
+    name.into()
+}
+
+pub struct Key(chacha20poly1305::Key);
+
+impl Key {
+    pub fn generate() -> Self {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// The key in `AOC_KEY` if that is set, or else the one in `.aoc-key`
+    pub fn load() -> io::Result<Self> {
+        match env::var(KEY_VAR) {
+            Ok(hex) if !hex.is_empty() => Key::parse(&hex),
+            _ => match fs::read_to_string(KEY_FILE) {
+                Ok(hex) => Key::parse(&hex),
+                Err(e) => Err(io::Error::new(
+                    e.kind(),
+                    format!("No key to decrypt inputs with in {KEY_VAR} or {KEY_FILE}: {e}"),
+                )),
+            },
+        }
+    }
+
+    pub fn parse(hex: &str) -> io::Result<Self> {
+        let hex = hex.trim();
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "A key is 64 hex digits");
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        Ok(Key(*chacha20poly1305::Key::from_slice(&bytes)))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Seals `plain` under a new nonce, so the same input encrypts differently each time
+    pub fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = ChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, plain)
+            .expect("inputs are far below the size limit");
+        [nonce.as_slice(), &sealed].concat()
+    }
+
+    pub fn decrypt(&self, sealed: &[u8]) -> io::Result<Vec<u8>> {
+        let failed = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Could not decrypt: wrong key or damaged file",
+            )
+        };
+        if sealed.len() < NONCE_LEN {
+            return Err(failed());
+        }
+
+        let (nonce, sealed) = sealed.split_at(NONCE_LEN);
+        ChaCha20Poly1305::new(&self.0)
+            .decrypt(Nonce::from_slice(nonce), sealed)
+            .map_err(|_| failed())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crypt::{encrypted_path, Key};
+    use std::path::Path;
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::generate();
+        let input = b"1abc2\npqr3stu8vwx\n";
+
+        let sealed = key.encrypt(input);
+        assert_ne!(&sealed[12..], input);
+        assert_ne!(key.encrypt(input), sealed);
+        assert_eq!(key.decrypt(&sealed).unwrap(), input);
+
+        assert!(Key::generate().decrypt(&sealed).is_err());
+        let mut damaged = sealed.clone();
+        damaged[20] ^= 1;
+        assert!(key.decrypt(&damaged).is_err());
+        assert!(key.decrypt(&sealed[..8]).is_err());
+    }
+
+    #[test]
+    fn test_key() {
+        let key = Key::generate();
+        let hex = key.to_hex();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(Key::parse(&format!("{hex}\n")).unwrap().to_hex(), hex);
+
+        assert!(Key::parse(&hex[2..]).is_err());
+        assert!(Key::parse(&format!("g{}", &hex[1..])).is_err());
+        assert!(Key::parse(&"é".repeat(32)).is_err());
+
+        assert_eq!(
+            encrypted_path(Path::new("data/2023/01.txt")),
+            Path::new("data/2023/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use crate::math::merge_congruence;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shape of the sequence `start, step(start), step(step(start)), ...`
+///
+/// The first `tail` states are visited once, after which the sequence repeats
+/// every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub tail: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest step index that reaches the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.tail {
+            n
+        } else {
+            self.tail + (n - self.tail) % self.length
+        }
+    }
+
+    /// State after `n` steps, walking at most `tail + length` steps
+    pub fn nth<S, F>(&self, start: S, mut step: F, n: usize) -> S
+    where
+        F: FnMut(&S) -> S,
+    {
+        (0..self.reduce(n)).fold(start, |state, _| step(&state))
+    }
+
+    /// Step indices of the first pass at which `is_target` holds
+    pub fn hits<S, F, P>(&self, start: S, mut step: F, mut is_target: P) -> Hits
+    where
+        F: FnMut(&S) -> S,
+        P: FnMut(&S) -> bool,
+    {
+        let mut state = start;
+        let mut steps = Vec::new();
+
+        for n in 0..self.tail + self.length {
+            if is_target(&state) {
+                steps.push(n);
+            }
+            state = step(&state);
+        }
+
+        Hits::new(*self, steps)
+    }
+}
+
+/// Floyd's tortoise and hare. Only keeps two states in memory
+pub fn floyd<S, F>(start: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // Walking from the start and the meeting point at the same pace meets at the cycle entry
+    let mut tail = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        tail += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { tail, length }
+}
+
+/// Brent's algorithm. Like [`floyd`], but usually needs fewer calls to `step`
+pub fn brent<S, F>(start: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Keep the hare `length` steps ahead so both meet at the cycle entry
+    let mut tail = 0;
+    tortoise = start.clone();
+    hare = (0..length).fold(start, |state, _| step(&state));
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        tail += 1;
+    }
+
+    Cycle { tail, length }
+}
+
+/// Every state of the first pass through a sequence, found by remembering each visited state
+#[derive(Debug)]
+pub struct History<S> {
+    states: Vec<S>,
+    cycle: Cycle,
+}
+
+impl<S> History<S> {
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// State after `n` steps
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    /// States of the first pass in the order they were visited
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// Step indices of the first pass at which `is_target` holds
+    pub fn hits<P>(&self, mut is_target: P) -> Hits
+    where
+        P: FnMut(&S) -> bool,
+    {
+        let steps = self
+            .states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| is_target(state))
+            .map(|(n, _)| n)
+            .collect();
+
+        Hits::new(self.cycle, steps)
+    }
+}
+
+/// Hash-based detection. Uses memory for every state before the repetition,
+/// but calls `step` exactly `tail + length` times
+pub fn find<S, F>(start: S, mut step: F) -> History<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    loop {
+        if let Some(&tail) = seen.get(&state) {
+            let length = states.len() - tail;
+            return History {
+                states,
+                cycle: Cycle { tail, length },
+            };
+        }
+
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Step indices at which a cyclic sequence is in a target state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hits {
+    cycle: Cycle,
+    /// Hits before the sequence enters the cycle. These happen once
+    pub tail: Vec<usize>,
+    /// Hits in the first pass through the cycle. These repeat every `cycle.length` steps
+    pub cycle_hits: Vec<usize>,
+}
+
+impl Hits {
+    fn new(cycle: Cycle, steps: Vec<usize>) -> Self {
+        let (tail, cycle_hits) = steps.into_iter().partition(|&n| n < cycle.tail);
+        Hits {
+            cycle,
+            tail,
+            cycle_hits,
+        }
+    }
+
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// Whether the sequence is in a target state after `n` steps
+    pub fn contains(&self, n: usize) -> bool {
+        if n < self.cycle.tail {
+            self.tail.binary_search(&n).is_ok()
+        } else {
+            self.cycle_hits.binary_search(&self.cycle.reduce(n)).is_ok()
+        }
+    }
+}
+
+/// The first step at which every sequence is in a target state at once is too large
+/// to work out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+/// Smallest step count at which every sequence is in a target state at the same time,
+/// `None` if that never happens. Fails with [`Overflow`] when the combined cycle of
+/// the sequences is longer than `i64::MAX`
+pub fn first_common_hit(hits: &[Hits]) -> Result<Option<usize>, Overflow> {
+    let Some(bound) = hits.iter().map(|h| h.cycle.tail).max() else {
+        return Ok(None);
+    };
+
+    // Before every sequence has settled into its cycle, check each hit directly
+    let mut early = hits
+        .iter()
+        .flat_map(|h| {
+            let cycle = (0..bound.div_ceil(h.cycle.length))
+                .flat_map(move |k| h.cycle_hits.iter().map(move |&n| n + k * h.cycle.length));
+            h.tail.iter().copied().chain(cycle)
+        })
+        .filter(|&n| n < bound)
+        .collect::<Vec<_>>();
+    early.sort_unstable();
+
+    if let Some(n) = early
+        .into_iter()
+        .find(|&n| hits.iter().all(|h| h.contains(n)))
+    {
+        return Ok(Some(n));
+    }
+
+    // From then on, every combination of cycle hits is a system of congruences. Each
+    // merge gives congruences modulo the lcm of the cycle lengths so far
+    let mut solutions = vec![(0i128, 1i128)];
+    for h in hits {
+        let length = h.cycle.length as i128;
+        solutions = solutions
+            .iter()
+            .flat_map(|&(x, m)| {
+                h.cycle_hits
+                    .iter()
+                    .filter_map(move |&n| merge_congruence(x, m, n as i128, length))
+            })
+            .collect();
+
+        match solutions.first() {
+            None => return Ok(None),
+            Some(&(_, lcm)) if lcm > i64::MAX as i128 => return Err(Overflow),
+            Some(_) => {}
+        }
+    }
+
+    solutions
+        .into_iter()
+        .map(|(x, m)| {
+            let (x, m) = (x as usize, m as usize);
+            if x >= bound {
+                Ok(x)
+            } else {
+                (bound - x)
+                    .div_ceil(m)
+                    .checked_mul(m)
+                    .and_then(|n| n.checked_add(x))
+                    .ok_or(Overflow)
+            }
+        })
+        .min_by_key(|n| n.unwrap_or(usize::MAX))
+        .transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cycle::{brent, find, first_common_hit, floyd, Cycle, Hits, Overflow};
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 2 -> ...
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_detection() {
+        let expected = Cycle { tail: 2, length: 5 };
+
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find(0, step).cycle(), expected);
+        assert_eq!(brent(2, step), Cycle { tail: 0, length: 5 });
+    }
+
+    #[test]
+    fn test_state_at() {
+        let history = find(0, step);
+        let cycle = history.cycle();
+
+        for n in 0..30 {
+            let walked = (0..n).fold(0, |s, _| step(&s));
+            assert_eq!(*history.state_at(n), walked);
+            assert_eq!(cycle.nth(0, step, n), walked);
+        }
+    }
+
+    #[test]
+    fn test_hits() {
+        let history = find(0, step);
+        let hits = history.hits(|&s| s % 3 == 0);
+
+        assert_eq!(hits.tail, vec![0]);
+        assert_eq!(hits.cycle_hits, vec![3, 6]);
+        assert_eq!(hits, history.cycle().hits(0, step, |&s| s % 3 == 0));
+        assert!(hits.contains(8));
+        assert!(!hits.contains(9));
+    }
+
+    #[test]
+    fn test_first_common_hit() {
+        // Hits at 3, 6 (mod 5) and at 4 (mod 7) first line up at 11
+        let a = find(0, step).hits(|&s| s % 3 == 0);
+        let b = find(0, |&s: &u32| (s + 1) % 7).hits(|&s| s == 4);
+
+        assert_eq!(first_common_hit(&[a.clone(), b]), Ok(Some(11)));
+        // A hit in the tail counts too
+        assert_eq!(first_common_hit(&[a.clone(), a.clone()]), Ok(Some(0)));
+
+        let never = find(0, step).hits(|&s| s == 1);
+        let c = find(0, step).hits(|&s| s == 5);
+        assert_eq!(first_common_hit(&[never, c]), Ok(None));
+        assert_eq!(first_common_hit(&[]), Ok(None));
+    }
+
+    #[test]
+    fn test_first_common_hit_overflow() {
+        let every = |length: usize, hit: usize| Hits {
+            cycle: Cycle { tail: 0, length },
+            tail: Vec::new(),
+            cycle_hits: vec![hit],
+        };
+        // Coprime cycles of about 2^32 each only line up after about 2^64 steps
+        let (p, q) = ((1 << 32) - 5, (1 << 32) + 15);
+        assert_eq!(first_common_hit(&[every(p, 1), every(q, 2)]), Err(Overflow));
+        // Ones that never line up are still told apart, however long the cycles
+        assert_eq!(
+            first_common_hit(&[every(2 * p, 1), every(2 * q, 2)]),
+            Ok(None)
+        );
+        // Just below the limit is fine
+        let (p, q) = ((1 << 31) - 1, (1 << 31) + 11);
+        let n = first_common_hit(&[every(p, 3), every(q, 4)])
+            .unwrap()
+            .unwrap();
+        assert_eq!((n % p, n % q), (3, 4));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::linalg::IntegerSystem;
+use crate::math::lcm;
+
+/// Finds non-negative integers `x` with `a * x == b` and `x[i] <= upper[i]` that
+/// minimize `sum(x)`, or `None` if there are no such `x`.
+///
+/// Gaussian elimination leaves a handful of free variables, which are then searched
+/// with branch and bound over their ranges. The pivot variables follow from them.
+pub fn minimize_sum(a: &[Vec<i64>], b: &[i64], upper: &[i64]) -> Option<Vec<i64>> {
+    let system = IntegerSystem::new(a, b);
+    if !system.is_consistent() {
+        return None;
+    }
+
+    let mut search = Search::new(&system, upper);
+    let mut values = vec![0; system.free.len()];
+    search.branch(0, &mut values);
+    search.best.map(|(_, solution)| solution)
+}
+
+/// Upper bound on each variable of `a * x == b` when every entry of `a` and `b` is
+/// non-negative: no variable can exceed any right hand side it contributes to
+pub fn non_negative_bounds(a: &[Vec<i64>], b: &[i64]) -> Vec<i64> {
+    let cols = a.first().map_or(0, Vec::len);
+
+    (0..cols)
+        .map(|c| {
+            a.iter()
+                .zip(b)
+                .filter(|(row, _)| row[c] > 0)
+                .map(|(row, &rhs)| rhs / row[c])
+                .min()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+struct Search<'a> {
+    system: &'a IntegerSystem,
+    upper: &'a [i64],
+    // The objective, multiplied by `scale`, is `offset + sum(cost[i] * free[i])`
+    offset: i64,
+    cost: Vec<i64>,
+    // Smallest possible objective contribution of the free variables from each depth on
+    min_cost_from: Vec<i64>,
+    // Range of sum(rows[r][free[i]] * free[i]) over the free variables from each depth on
+    reach_from: Vec<Vec<(i64, i64)>>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl<'a> Search<'a> {
+    fn new(system: &'a IntegerSystem, upper: &'a [i64]) -> Self {
+        let pivot_coefficients = system
+            .pivots
+            .iter()
+            .enumerate()
+            .map(|(r, &p)| system.rows[r][p])
+            .collect::<Vec<_>>();
+
+        // Scale so every pivot variable has an integer weight in the objective
+        let scale = pivot_coefficients.iter().fold(1, |acc, &c| lcm(acc, c));
+        let weights = pivot_coefficients
+            .iter()
+            .map(|&c| scale / c)
+            .collect::<Vec<_>>();
+
+        let offset = weights.iter().zip(&system.rhs).map(|(w, r)| w * r).sum();
+        let cost = system
+            .free
+            .iter()
+            .map(|&f| {
+                scale
+                    - weights
+                        .iter()
+                        .zip(&system.rows)
+                        .map(|(w, row)| w * row[f])
+                        .sum::<i64>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut min_cost_from = vec![0; system.free.len() + 1];
+        let mut reach_from = vec![vec![(0, 0); system.rank()]; system.free.len() + 1];
+        for (i, &f) in system.free.iter().enumerate().rev() {
+            min_cost_from[i] = min_cost_from[i + 1] + (cost[i] * upper[f]).min(0);
+
+            reach_from[i] = reach_from[i + 1]
+                .iter()
+                .zip(&system.rows)
+                .map(|(&(lo, hi), row)| {
+                    let extent = row[f] * upper[f];
+                    (lo + extent.min(0), hi + extent.max(0))
+                })
+                .collect();
+        }
+
+        Search {
+            system,
+            upper,
+            offset,
+            cost,
+            min_cost_from,
+            reach_from,
+            best: None,
+        }
+    }
+
+    fn branch(&mut self, depth: usize, values: &mut [i64]) {
+        let partial = self.offset + (0..depth).map(|i| self.cost[i] * values[i]).sum::<i64>();
+
+        if let Some((best, _)) = &self.best {
+            if partial + self.min_cost_from[depth] >= *best {
+                return;
+            }
+        }
+
+        if !self.can_reach(depth, values) {
+            return;
+        }
+
+        if depth == values.len() {
+            self.accept(partial, values);
+            return;
+        }
+
+        let bound = self.upper[self.system.free[depth]];
+        // Try the cheapest values first so a good bound is found early
+        let order: Box<dyn Iterator<Item = i64>> = if self.cost[depth] >= 0 {
+            Box::new(0..=bound)
+        } else {
+            Box::new((0..=bound).rev())
+        };
+
+        for value in order {
+            values[depth] = value;
+            self.branch(depth + 1, values);
+        }
+        values[depth] = 0;
+    }
+
+    // Whether the remaining free variables can still bring every pivot variable
+    // into `0..=upper`
+    fn can_reach(&self, depth: usize, values: &[i64]) -> bool {
+        self.system
+            .rows
+            .iter()
+            .zip(&self.system.rhs)
+            .zip(&self.system.pivots)
+            .zip(&self.reach_from[depth])
+            .all(|(((row, &rhs), &pivot), &(lo, hi))| {
+                let assigned = self.system.free[..depth]
+                    .iter()
+                    .zip(values)
+                    .map(|(&f, &v)| row[f] * v)
+                    .sum::<i64>();
+
+                // row[pivot] * x[pivot] == rhs - assigned - rest must land in range
+                let needed_max = rhs - assigned;
+                let needed_min = needed_max - row[pivot] * self.upper[pivot];
+                lo <= needed_max && hi >= needed_min
+            })
+    }
+
+    fn accept(&mut self, objective: i64, values: &[i64]) {
+        let Some(solution) = self.system.substitute(values) else {
+            return;
+        };
+
+        let in_bounds = self
+            .system
+            .pivots
+            .iter()
+            .all(|&p| (0..=self.upper[p]).contains(&solution[p]));
+
+        if in_bounds {
+            self.best = Some((objective, solution));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ilp::{minimize_sum, non_negative_bounds};
+
+    #[test]
+    fn test_minimize_sum() {
+        // Counters are rows, buttons are columns
+        let a = vec![
+            vec![0, 0, 0, 0, 1, 1],
+            vec![0, 1, 0, 0, 0, 1],
+            vec![0, 0, 1, 1, 1, 0],
+            vec![1, 1, 0, 1, 0, 0],
+        ];
+        let b = vec![3, 5, 4, 7];
+        let upper = non_negative_bounds(&a, &b);
+        assert_eq!(upper, vec![7, 5, 4, 4, 3, 3]);
+
+        let solution = minimize_sum(&a, &b, &upper).unwrap();
+        assert_eq!(solution.iter().sum::<i64>(), 10);
+        assert!(a.iter().zip(&b).all(|(row, &rhs)| row
+            .iter()
+            .zip(&solution)
+            .map(|(x, y)| x * y)
+            .sum::<i64>()
+            == rhs));
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // x + y == 3 and x - y == 0 only has a fractional solution
+        assert_eq!(
+            minimize_sum(&[vec![1, 1], vec![1, -1]], &[3, 0], &[3, 3]),
+            None
+        );
+        // x == 5 is out of bounds
+        assert_eq!(minimize_sum(&[vec![1]], &[5], &[4]), None);
+    }
+}
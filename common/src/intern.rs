@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+/// Maps labels such as `"AAA"` or `"svr"` to dense ids `0, 1, 2, ...` in the order
+/// they are first seen, so per-label data can live in plain vectors
+#[derive(Debug, Default, Clone)]
+pub struct Interner<'a> {
+    ids: HashMap<&'a str, u32>,
+    labels: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Id of `label`, assigning the next free one if it is new
+    pub fn intern(&mut self, label: &'a str) -> u32 {
+        *self.ids.entry(label).or_insert_with(|| {
+            self.labels.push(label);
+            (self.labels.len() - 1) as u32
+        })
+    }
+
+    /// Id of `label`, if it has been interned
+    pub fn get(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).copied()
+    }
+
+    /// Label of `id`. Panics on an id this interner did not hand out
+    pub fn label(&self, id: u32) -> &'a str {
+        self.labels[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Every `(id, label)`, in id order
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &'a str)> + '_ {
+        self.labels
+            .iter()
+            .enumerate()
+            .map(|(id, &l)| (id as u32, l))
+    }
+}
+
+/// Directed graph over interned labels, stored as one adjacency list per id
+#[derive(Debug, Default, Clone)]
+pub struct Graph<'a> {
+    names: Interner<'a>,
+    edges: Vec<Vec<u32>>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// Graph from each label and the labels it points to, in order, e.g. the lines of
+    /// `svr: aaa bbb`. Labels that only appear as targets get no outgoing edges
+    pub fn from_adjacency<I, T>(adjacency: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, T)>,
+        T: IntoIterator<Item = &'a str>,
+    {
+        let mut graph = Graph::new();
+        for (from, targets) in adjacency {
+            let from = graph.add_node(from);
+            for to in targets {
+                let to = graph.add_node(to);
+                graph.edges[from as usize].push(to);
+            }
+        }
+        graph
+    }
+
+    /// Id of `label`, adding it without edges if it is new
+    pub fn add_node(&mut self, label: &'a str) -> u32 {
+        let id = self.names.intern(label);
+        if id as usize == self.edges.len() {
+            self.edges.push(Vec::new());
+        }
+        id
+    }
+
+    pub fn add_edge(&mut self, from: &'a str, to: &'a str) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges[from as usize].push(to);
+    }
+
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.names.get(label)
+    }
+
+    pub fn label(&self, id: u32) -> &'a str {
+        self.names.label(id)
+    }
+
+    /// Targets of the edges out of `id`, in the order they were added
+    pub fn neighbors(&self, id: u32) -> &[u32] {
+        &self.edges[id as usize]
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Every node id with its label
+    pub fn nodes(&self) -> impl Iterator<Item = (u32, &'a str)> + '_ {
+        self.names.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::intern::{Graph, Interner};
+
+    #[test]
+    fn test_interner() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("svr"), 0);
+        assert_eq!(names.intern("out"), 1);
+        assert_eq!(names.intern("svr"), 0);
+        assert_eq!(names.get("out"), Some(1));
+        assert_eq!(names.get("you"), None);
+        assert_eq!(names.label(1), "out");
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            vec![(0, "svr"), (1, "out")]
+        );
+    }
+
+    #[test]
+    fn test_graph() {
+        let input = "aaa: you hhh\nyou: bbb\nhhh: out";
+        let graph = Graph::from_adjacency(input.lines().map(|line| {
+            let (from, to) = line.split_once(": ").unwrap();
+            (from, to.split_whitespace())
+        }));
+
+        assert_eq!(graph.len(), 5);
+        let aaa = graph.id("aaa").unwrap();
+        let labels = graph
+            .neighbors(aaa)
+            .iter()
+            .map(|&id| graph.label(id))
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["you", "hhh"]);
+        assert!(graph.neighbors(graph.id("out").unwrap()).is_empty());
+
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("b", "a");
+        assert_eq!(graph.neighbors(1), &[0]);
+    }
+}
@@ -0,0 +1,180 @@
+use std::marker::PhantomData;
+
+/// Integer types that can be pulled out of text by [`ints`]
+pub trait Extract: Copy {
+    /// Whether a `-` right before the digits makes the number negative
+    const SIGNED: bool;
+
+    fn from_digit(digit: u8) -> Self;
+
+    /// `self * 10 + digit`, or `self * 10 - digit` while reading a negative number.
+    /// `None` if that does not fit
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Extract for $t {
+            const SIGNED: bool = false;
+
+            fn from_digit(digit: u8) -> Self {
+                digit as $t
+            }
+
+            fn push_digit(self, digit: u8, _negative: bool) -> Option<Self> {
+                self.checked_mul(10)?.checked_add(digit as $t)
+            }
+        })*
+    };
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(impl Extract for $t {
+            const SIGNED: bool = true;
+
+            fn from_digit(digit: u8) -> Self {
+                digit as $t
+            }
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                // Counting down for negative numbers reaches `MIN`, which has no positive twin
+                let shifted = self.checked_mul(10)?;
+                if negative {
+                    shifted.checked_sub(digit as $t)
+                } else {
+                    shifted.checked_add(digit as $t)
+                }
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Every integer in `s`, in order, skipping whatever lies between them.
+///
+/// For signed types a `-` directly before the digits is a minus sign, unless it
+/// follows another digit: `3-5` is a range and yields `3` and `5`, while `x=-5`
+/// yields `-5`. Unsigned types always treat `-` as a separator.
+///
+/// Panics on a number that does not fit in `T`.
+pub fn ints<T: Extract>(s: &str) -> Ints<'_, T> {
+    Ints {
+        bytes: s.as_bytes(),
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// The first `N` integers of `s`, or `None` if it has a different number of them
+pub fn ints_array<T: Extract, const N: usize>(s: &str) -> Option<[T; N]> {
+    let mut iter = ints(s);
+    let mut values = [T::from_digit(0); N];
+
+    for value in values.iter_mut() {
+        *value = iter.next()?;
+    }
+
+    iter.next().is_none().then_some(values)
+}
+
+/// The integers of each line of `s` as a fixed-size array, e.g. `[i64; 3]` for
+/// lines like `162,817,812`
+///
+/// Panics if a line does not hold exactly `N` integers
+pub fn ints_per_line<T: Extract, const N: usize>(s: &str) -> impl Iterator<Item = [T; N]> + '_ {
+    s.lines()
+        .map(|line| ints_array(line).unwrap_or_else(|| panic!("expected {N} integers in {line:?}")))
+}
+
+/// Iterator returned by [`ints`]
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Extract> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let start = self.pos + self.bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let digits = self.bytes[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        self.pos = start + digits;
+
+        let negative = T::SIGNED
+            && start > 0
+            && self.bytes[start - 1] == b'-'
+            && (start == 1 || !self.bytes[start - 2].is_ascii_digit());
+
+        let value = self.bytes[start..self.pos]
+            .iter()
+            .try_fold(T::from_digit(0), |value, &b| {
+                value.push_digit(b - b'0', negative)
+            });
+        if value.is_none() {
+            // Skipping it would shift every later number along, so panic rather than
+            // give a wrong answer
+            let text = std::str::from_utf8(&self.bytes[start - negative as usize..self.pos])
+                .expect("digits are ASCII");
+            panic!("{text} does not fit in {}", std::any::type_name::<T>());
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ints::{ints, ints_array, ints_per_line};
+
+    #[test]
+    fn test_ints() {
+        let values = ints::<i64>("p=0,-4 v=3,-3 1-3 a: -0").collect::<Vec<_>>();
+        assert_eq!(values, vec![0, -4, 3, -3, 1, 3, 0]);
+
+        let values = ints::<u32>("Time:      7  15   30\n-2").collect::<Vec<_>>();
+        assert_eq!(values, vec![7, 15, 30, 2]);
+
+        assert_eq!(ints::<u8>("no numbers - here").next(), None);
+        assert_eq!(ints::<i64>("-").next(), None);
+        assert_eq!(ints::<i64>("-12").next(), Some(-12));
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(ints::<u8>("255 0").collect::<Vec<_>>(), vec![255, 0]);
+        assert_eq!(ints::<i8>("-128 127").collect::<Vec<_>>(), vec![-128, 127]);
+        assert_eq!(
+            ints::<i64>("-9223372036854775808 1").collect::<Vec<_>>(),
+            vec![i64::MIN, 1]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "256 does not fit in u8")]
+    fn test_too_big() {
+        ints::<u8>("255 256 7").for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "-129 does not fit in i8")]
+    fn test_too_small() {
+        ints::<i8>("-128 -129").for_each(drop);
+    }
+
+    #[test]
+    fn test_arrays() {
+        assert_eq!(ints_array::<i64, 3>("162,817,-812"), Some([162, 817, -812]));
+        assert_eq!(ints_array::<i64, 3>("162,817"), None);
+        assert_eq!(ints_array::<i64, 2>("162,817,812"), None);
+
+        let points = ints_per_line::<u16, 2>("7,1\n11,1\n").collect::<Vec<_>>();
+        assert_eq!(points, vec![[7, 1], [11, 1]]);
+    }
+}
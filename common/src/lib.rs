@@ -1,3 +1,27 @@
+// Lets code generated by `aoc-derive` refer to `::common` from inside this crate too
+extern crate self as common;
+
+pub mod answers;
+pub mod automaton;
+pub mod bits;
+pub mod compress;
+#[cfg(feature = "encrypted-inputs")]
+pub mod crypt;
+pub mod cycle;
+pub mod ilp;
+pub mod intern;
+pub mod ints;
+pub mod linalg;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod polygon;
+pub mod rational;
+pub mod record;
+pub mod sequence;
+#[cfg(feature = "viz")]
+pub mod viz;
+
 use std::fmt::{Display, Formatter};
 use std::path::Path;
 use std::{fs, io};
@@ -59,8 +83,20 @@ pub trait Solution {
     fn part_two(&self, input: &str) -> Answer;
 }
 
+/// Reads an input. With `encrypted-inputs`, decrypts its `.enc` copy from `crypt` if
+/// there is no plain one
 pub fn parse_file(file: &Path) -> io::Result<String> {
-    fs::read_to_string(file)
+    match fs::read_to_string(file) {
+        #[cfg(feature = "encrypted-inputs")]
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let Ok(sealed) = fs::read(crypt::encrypted_path(file)) else {
+                return Err(e);
+            };
+            let plain = crypt::Key::load()?.decrypt(&sealed)?;
+            String::from_utf8(plain).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+        read => read,
+    }
 }
 
 pub fn load_file(year: u32, day: u32) -> io::Result<String> {
@@ -68,15 +104,3 @@ pub fn load_file(year: u32, day: u32) -> io::Result<String> {
     let file = Path::new(&file_name);
     parse_file(file)
 }
-
-fn gcd(a: usize, b: usize) -> usize {
-    match b {
-        0 => a,
-        // Rerun the function with b and the remainder of a / b
-        _ => gcd(b, a % b),
-    }
-}
-
-pub fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
-}
@@ -0,0 +1,541 @@
+use crate::math::gcd;
+use crate::rational::Ratio;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Vector over GF(2), packed 64 entries to a word
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVector {
+    pub fn new(len: usize) -> Self {
+        BitVector {
+            len,
+            words: vec![0; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn from_bools(bits: &[bool]) -> Self {
+        let mut vector = BitVector::new(bits.len());
+        bits.iter()
+            .enumerate()
+            .filter(|(_, &bit)| bit)
+            .for_each(|(i, _)| vector.set(i, true));
+        vector
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "index {i} out of bounds for length {}",
+            self.len
+        );
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(
+            i < self.len,
+            "index {i} out of bounds for length {}",
+            self.len
+        );
+        let mask = 1 << (i % WORD_BITS);
+        if value {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// Adds `other` to this vector, which over GF(2) is a xor
+    pub fn add(&mut self, other: &BitVector) {
+        assert_eq!(self.len, other.len, "vector lengths differ");
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a ^= b);
+    }
+
+    /// Number of entries set to one
+    pub fn weight(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+}
+
+/// Matrix over GF(2), each row packed into 64-bit words
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl BitMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+        BitMatrix {
+            rows,
+            cols,
+            words_per_row,
+            data: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// Builds a matrix with `cols` columns from its rows
+    pub fn from_rows(cols: usize, rows: &[Vec<bool>]) -> Self {
+        let mut matrix = BitMatrix::new(rows.len(), cols);
+        for (r, row) in rows.iter().enumerate() {
+            assert_eq!(row.len(), cols, "row {r} has the wrong number of columns");
+            row.iter()
+                .enumerate()
+                .filter(|(_, &bit)| bit)
+                .for_each(|(c, _)| matrix.set(r, c, true));
+        }
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) out of bounds"
+        );
+        self.data[row * self.words_per_row + col / WORD_BITS] >> (col % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) out of bounds"
+        );
+        let word = &mut self.data[row * self.words_per_row + col / WORD_BITS];
+        let mask = 1 << (col % WORD_BITS);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /// Copy of the matrix with `rhs` appended as an extra column
+    pub fn augment(&self, rhs: &BitVector) -> BitMatrix {
+        assert_eq!(rhs.len(), self.rows, "right hand side has the wrong length");
+
+        let mut augmented = BitMatrix::new(self.rows, self.cols + 1);
+        for r in 0..self.rows {
+            let (src, dst) = (self.row(r), augmented.row_mut(r));
+            dst[..src.len()].copy_from_slice(src);
+            if rhs.get(r) {
+                augmented.set(r, self.cols, true);
+            }
+        }
+        augmented
+    }
+
+    fn row(&self, r: usize) -> &[u64] {
+        &self.data[r * self.words_per_row..(r + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, r: usize) -> &mut [u64] {
+        &mut self.data[r * self.words_per_row..(r + 1) * self.words_per_row]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            (0..self.words_per_row).for_each(|w| {
+                self.data
+                    .swap(a * self.words_per_row + w, b * self.words_per_row + w)
+            });
+        }
+    }
+
+    // dst ^= src
+    fn add_row(&mut self, dst: usize, src: usize) {
+        for w in 0..self.words_per_row {
+            let word = self.data[src * self.words_per_row + w];
+            self.data[dst * self.words_per_row + w] ^= word;
+        }
+    }
+
+    /// Brings the first `cols` columns into reduced row echelon form.
+    ///
+    /// Returns the pivot column of each of the first `rank` rows
+    pub fn row_reduce(&mut self, cols: usize) -> Vec<usize> {
+        let mut pivots = Vec::new();
+
+        for col in 0..cols.min(self.cols) {
+            let pivot_row = pivots.len();
+            let Some(found) = (pivot_row..self.rows).find(|&r| self.get(r, col)) else {
+                continue;
+            };
+
+            self.swap_rows(pivot_row, found);
+            for r in 0..self.rows {
+                if r != pivot_row && self.get(r, col) {
+                    self.add_row(r, pivot_row);
+                }
+            }
+            pivots.push(col);
+        }
+
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce(self.cols).len()
+    }
+
+    /// Basis of the vectors `x` with `self * x == 0`
+    pub fn nullspace(&self) -> Vec<BitVector> {
+        let mut reduced = self.clone();
+        let pivots = reduced.row_reduce(self.cols);
+        reduced.nullspace_from_reduced(&pivots, self.cols)
+    }
+
+    fn nullspace_from_reduced(&self, pivots: &[usize], cols: usize) -> Vec<BitVector> {
+        let mut is_pivot = vec![false; cols];
+        pivots.iter().for_each(|&c| is_pivot[c] = true);
+
+        (0..cols)
+            .filter(|&c| !is_pivot[c])
+            .map(|free| {
+                let mut vector = BitVector::new(cols);
+                vector.set(free, true);
+                pivots
+                    .iter()
+                    .enumerate()
+                    .filter(|&(r, _)| self.get(r, free))
+                    .for_each(|(_, &col)| vector.set(col, true));
+                vector
+            })
+            .collect()
+    }
+
+    /// All solutions of `self * x == rhs`, or `None` if there are none
+    pub fn solve(&self, rhs: &BitVector) -> Option<Gf2Solution> {
+        let mut reduced = self.augment(rhs);
+        let pivots = reduced.row_reduce(self.cols);
+
+        // Rows past the rank are zero on the left, so a one on the right is a contradiction
+        if (pivots.len()..self.rows).any(|r| reduced.get(r, self.cols)) {
+            return None;
+        }
+
+        let mut particular = BitVector::new(self.cols);
+        pivots
+            .iter()
+            .enumerate()
+            .for_each(|(r, &col)| particular.set(col, reduced.get(r, self.cols)));
+
+        Some(Gf2Solution {
+            nullspace: reduced.nullspace_from_reduced(&pivots, self.cols),
+            particular,
+        })
+    }
+}
+
+/// Solution set of a GF(2) system: `particular` plus any combination of `nullspace`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Solution {
+    pub particular: BitVector,
+    pub nullspace: Vec<BitVector>,
+}
+
+impl Gf2Solution {
+    /// Smallest number of ones over every solution. Tries all `2^nullspace.len()` of them,
+    /// so panics when there are 64 or more free variables
+    pub fn min_weight(&self) -> usize {
+        assert!(
+            self.nullspace.len() < 64,
+            "{} free variables are too many to try every solution",
+            self.nullspace.len()
+        );
+
+        let mut candidate = self.particular.clone();
+        let mut best = candidate.weight();
+
+        // Walk the combinations in Gray code order so each one differs by a single basis vector
+        for i in 1u64..1 << self.nullspace.len() {
+            candidate.add(&self.nullspace[i.trailing_zeros() as usize]);
+            best = best.min(candidate.weight());
+        }
+
+        best
+    }
+}
+
+/// Integer system `a * x == b` in fraction-free reduced row echelon form.
+///
+/// Every pivot row `r` reads `rows[r][pivots[r]] * x[pivots[r]] + sum(rows[r][f] * x[f]) == rhs[r]`
+/// over the free columns `f`, with a positive pivot coefficient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegerSystem {
+    pub rows: Vec<Vec<i64>>,
+    pub rhs: Vec<i64>,
+    pub pivots: Vec<usize>,
+    pub free: Vec<usize>,
+    consistent: bool,
+}
+
+impl IntegerSystem {
+    pub fn new(a: &[Vec<i64>], b: &[i64]) -> Self {
+        assert_eq!(a.len(), b.len(), "right hand side has the wrong length");
+        let cols = a.first().map_or(0, Vec::len);
+
+        // Work on augmented rows, so the right hand side follows every row operation
+        let mut matrix = a
+            .iter()
+            .zip(b)
+            .map(|(row, &rhs)| {
+                assert_eq!(row.len(), cols, "rows have different lengths");
+                row.iter().copied().chain(std::iter::once(rhs)).collect()
+            })
+            .collect::<Vec<Vec<i64>>>();
+
+        let mut pivots = Vec::new();
+        for col in 0..cols {
+            let pivot_row = pivots.len();
+            let Some(found) = (pivot_row..matrix.len()).find(|&r| matrix[r][col] != 0) else {
+                continue;
+            };
+            matrix.swap(pivot_row, found);
+
+            // Keep pivots positive, cross-multiplying by a negative one would flip earlier rows
+            normalize(&mut matrix[pivot_row]);
+            if matrix[pivot_row][col] < 0 {
+                matrix[pivot_row].iter_mut().for_each(|x| *x = -*x);
+            }
+
+            let pivot = matrix[pivot_row].clone();
+            for (r, row) in matrix.iter_mut().enumerate() {
+                if r == pivot_row || row[col] == 0 {
+                    continue;
+                }
+
+                // Cross-multiply to cancel the column without leaving the integers
+                let factor = row[col];
+                row.iter_mut()
+                    .zip(&pivot)
+                    .for_each(|(x, &p)| *x = *x * pivot[col] - p * factor);
+                normalize(row);
+            }
+            pivots.push(col);
+        }
+
+        let consistent = matrix[pivots.len()..].iter().all(|row| row[cols] == 0);
+        let free = (0..cols).filter(|c| !pivots.contains(c)).collect();
+        matrix.truncate(pivots.len());
+        let rhs = matrix.iter_mut().map(|row| row.pop().unwrap()).collect();
+
+        IntegerSystem {
+            rows: matrix,
+            rhs,
+            pivots,
+            free,
+            consistent,
+        }
+    }
+
+    /// Whether the system has any rational solution at all
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Fills in the pivot variables for the given values of the free variables, in the
+    /// order of `free`. Returns `None` if that would need a non-integer value
+    pub fn substitute(&self, free_values: &[i64]) -> Option<Vec<i64>> {
+        assert_eq!(
+            free_values.len(),
+            self.free.len(),
+            "wrong number of free values"
+        );
+
+        let cols = self.pivots.len() + self.free.len();
+        let mut solution = vec![0; cols];
+        self.free
+            .iter()
+            .zip(free_values)
+            .for_each(|(&f, &v)| solution[f] = v);
+
+        for ((row, &rhs), &pivot) in self.rows.iter().zip(&self.rhs).zip(&self.pivots) {
+            let remainder = rhs - self.free.iter().map(|&f| row[f] * solution[f]).sum::<i64>();
+
+            if remainder % row[pivot] != 0 {
+                return None;
+            }
+            solution[pivot] = remainder / row[pivot];
+        }
+
+        Some(solution)
+    }
+
+    /// Like [`IntegerSystem::substitute`], but solves for the pivot variables exactly
+    /// even when they come out fractional
+    pub fn substitute_rational(&self, free_values: &[Ratio]) -> Vec<Ratio> {
+        assert_eq!(
+            free_values.len(),
+            self.free.len(),
+            "wrong number of free values"
+        );
+
+        let cols = self.pivots.len() + self.free.len();
+        let mut solution = vec![Ratio::ZERO; cols];
+        self.free
+            .iter()
+            .zip(free_values)
+            .for_each(|(&f, &v)| solution[f] = v);
+
+        for ((row, &rhs), &pivot) in self.rows.iter().zip(&self.rhs).zip(&self.pivots) {
+            let remainder = Ratio::from(rhs)
+                - self
+                    .free
+                    .iter()
+                    .map(|&f| Ratio::from(row[f]) * solution[f])
+                    .sum::<Ratio>();
+            solution[pivot] = remainder / Ratio::from(row[pivot]);
+        }
+
+        solution
+    }
+}
+
+// Divides a row by the gcd of its entries to keep the numbers small
+fn normalize(row: &mut [i64]) {
+    let divisor = row.iter().fold(0, |acc, &x| gcd(acc, x));
+    if divisor > 1 {
+        row.iter_mut().for_each(|x| *x /= divisor);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::linalg::{BitMatrix, BitVector, IntegerSystem};
+    use crate::rational::Ratio;
+
+    fn bits(s: &str) -> Vec<bool> {
+        s.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn test_bit_vector() {
+        let mut a = BitVector::new(70);
+        a.set(3, true);
+        a.set(69, true);
+        assert!(a.get(69));
+        assert_eq!(a.weight(), 2);
+
+        let b = BitVector::from_bools(&bits("0001"));
+        assert_eq!(b.iter().collect::<Vec<_>>(), bits("0001"));
+    }
+
+    #[test]
+    fn test_rank_and_nullspace() {
+        let matrix = BitMatrix::from_rows(3, &[bits("110"), bits("011"), bits("101")]);
+
+        assert_eq!(matrix.rank(), 2);
+
+        let nullspace = matrix.nullspace();
+        assert_eq!(nullspace, vec![BitVector::from_bools(&bits("111"))]);
+    }
+
+    #[test]
+    fn test_solve() {
+        // Lights are rows, buttons are columns
+        let matrix = BitMatrix::from_rows(
+            6,
+            &[
+                bits("000011"),
+                bits("010001"),
+                bits("001110"),
+                bits("110100"),
+            ],
+        );
+        let target = BitVector::from_bools(&bits("0110"));
+
+        let solution = matrix.solve(&target).unwrap();
+        assert_eq!(solution.nullspace.len(), 2);
+        assert_eq!(solution.min_weight(), 2);
+
+        let inconsistent = BitMatrix::from_rows(1, &[bits("1"), bits("1")]);
+        assert!(inconsistent
+            .solve(&BitVector::from_bools(&bits("10")))
+            .is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "64 free variables")]
+    fn test_min_weight_too_many_free() {
+        // One equation over 65 variables leaves 64 of them free
+        let matrix = BitMatrix::from_rows(65, &[vec![true; 65]]);
+        let solution = matrix.solve(&BitVector::from_bools(&[true])).unwrap();
+        solution.min_weight();
+    }
+
+    #[test]
+    fn test_integer_system() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let system = IntegerSystem::new(
+            &[vec![1, 1, 1], vec![0, 2, 5], vec![2, 5, -1]],
+            &[6, -4, 27],
+        );
+        assert!(system.is_consistent());
+        assert!(system.free.is_empty());
+        assert_eq!(system.substitute(&[]), Some(vec![5, 3, -2]));
+
+        // A negative pivot must not flip the sign of earlier pivot rows
+        let system = IntegerSystem::new(&[vec![1, 1, 0], vec![2, -1, 1]], &[3, 0]);
+        assert!(system
+            .pivots
+            .iter()
+            .enumerate()
+            .all(|(r, &p)| system.rows[r][p] > 0));
+        assert_eq!(system.substitute(&[3]), Some(vec![0, 3, 3]));
+
+        // x + y = 3 leaves y free
+        let system = IntegerSystem::new(&[vec![1, 1]], &[3]);
+        assert_eq!(system.free, vec![1]);
+        assert_eq!(system.substitute(&[1]), Some(vec![2, 1]));
+
+        // 2x = 3 has no integer solution, only a rational one
+        let system = IntegerSystem::new(&[vec![2]], &[3]);
+        assert_eq!(system.substitute(&[]), None);
+        assert_eq!(system.substitute_rational(&[]), vec![Ratio::new(3, 2)]);
+
+        // 2x + y = 4 with y = 1/3
+        let system = IntegerSystem::new(&[vec![2, 1]], &[4]);
+        assert_eq!(
+            system.substitute_rational(&[Ratio::new(1, 3)]),
+            vec![Ratio::new(11, 6), Ratio::new(1, 3)]
+        );
+
+        let system = IntegerSystem::new(&[vec![1], vec![1]], &[1, 2]);
+        assert!(!system.is_consistent());
+    }
+}
@@ -0,0 +1,428 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::{Div, RangeInclusive, Rem};
+
+/// Primitive integer types the number theory helpers can work with
+pub trait Integer: Copy + Ord + Debug + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Absolute value, or `None` when it cannot be represented (e.g. `i64::MIN`)
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+        })*
+    };
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                <$t>::checked_abs(self)
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is 0
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd overflowed")
+}
+
+/// Like [`gcd`], but returns `None` when the absolute value of an input does not fit in `T`
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a.checked_abs()?, b.checked_abs()?);
+
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    Some(a)
+}
+
+/// Least common multiple, always non-negative. Panics if the result does not fit in `T`
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// Like [`lcm`], but returns `None` instead of overflowing
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    // Divide before multiplying so only a result that is genuinely too big overflows
+    let (a, b) = (a.checked_abs()?, b.checked_abs()?);
+    (a / checked_gcd(a, b)?).checked_mul(b)
+}
+
+/// Extended Euclidean algorithm. Returns `(g, x, y)` such that `a * x + b * y == g`,
+/// where `g` is the non-negative gcd of `a` and `b`. Panics if `g` is `2^63`, which
+/// only happens when both inputs are `0` or `i64::MIN`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    checked_extended_gcd(a, b).expect("gcd does not fit in i64")
+}
+
+/// Like [`extended_gcd`], but returns `None` when the result does not fit in `i64`
+pub fn checked_extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    Some((
+        i64::try_from(g).ok()?,
+        i64::try_from(x).ok()?,
+        i64::try_from(y).ok()?,
+    ))
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Non-negative remainder of `a` divided by `m`
+pub fn modulo(a: i64, m: i64) -> i64 {
+    a.rem_euclid(m)
+}
+
+/// `a * b mod m` without intermediate overflow
+pub fn mod_mul(a: i64, b: i64, m: i64) -> i64 {
+    ((a as i128 * b as i128).rem_euclid(m as i128)) as i64
+}
+
+/// `base ^ exp mod m` by repeated squaring
+pub fn mod_pow(base: i64, exp: u64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+
+    let mut result = 1 % m;
+    let mut base = modulo(base, m);
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Multiplicative inverse of `a` modulo `m`, if `a` and `m` are coprime
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    (g == 1).then(|| modulo(x, m))
+}
+
+/// Chinese Remainder Theorem over `(residue, modulus)` pairs.
+///
+/// Moduli do not need to be coprime. Returns the smallest non-negative `x` and the
+/// combined modulus (the lcm of all moduli), or `None` if the congruences contradict
+/// each other or the combined modulus does not fit in an `i64`
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0i128, 1i128), |(x, m), &(residue, modulus)| {
+            assert!(modulus > 0, "modulus must be positive");
+            merge_congruence(x, m, residue as i128, modulus as i128)
+                .filter(|&(_, lcm)| lcm <= i64::MAX as i128)
+        })
+        .map(|(x, m)| (x as i64, m as i64))
+}
+
+/// Merges `x ≡ a1 (mod m1)` with `x ≡ a2 (mod m2)` into one congruence modulo their lcm,
+/// or `None` if they contradict each other. Cannot overflow while the residues and
+/// moduli are below `2^63`, though the merged modulus can be beyond that
+pub(crate) fn merge_congruence(a1: i128, m1: i128, a2: i128, m2: i128) -> Option<(i128, i128)> {
+    let (g, p, _) = extended_gcd_wide(m1, m2);
+    let diff = a2 - a1;
+
+    if diff % g != 0 {
+        return None;
+    }
+
+    let lcm = m1 / g * m2;
+
+    // m1 * p ≡ g (mod m2), so stepping a1 by m1 * p * diff / g lands on a2 modulo m2
+    let step = (diff / g * p).rem_euclid(m2 / g);
+    Some(((a1 + m1 * step).rem_euclid(lcm), lcm))
+}
+
+/// Number of integers `x` in `range` where `a * x^2 + b * x + c > threshold`, in
+/// constant time.
+///
+/// The roots come from an exact integer square root of the discriminant and are then
+/// nudged onto the right integers by evaluating the quadratic exactly, so there is no
+/// floating point error to worry about. Intermediate values are `i128`, which is enough
+/// unless the coefficients are around `2^40` and more at once, and then this panics
+pub fn count_quadratic_above(
+    a: i64,
+    b: i64,
+    c: i64,
+    threshold: i64,
+    range: RangeInclusive<i64>,
+) -> u64 {
+    checked_count_quadratic_above(a, b, c, threshold, range)
+        .expect("quadratic overflows i128 or the count u64")
+}
+
+/// Like [`count_quadratic_above`], but returns `None` when an intermediate value does
+/// not fit in `i128`, or the count, all of a full `i64` range, does not fit in `u64`
+pub fn checked_count_quadratic_above(
+    a: i64,
+    b: i64,
+    c: i64,
+    threshold: i64,
+    range: RangeInclusive<i64>,
+) -> Option<u64> {
+    let (a, b, c) = (a as i128, b as i128, c as i128 - threshold as i128);
+    let range = (*range.start() as i128, *range.end() as i128);
+
+    match a.cmp(&0) {
+        Ordering::Less => overlap(range, cap(a, b, c, true)?),
+        // Everything except where the upside down parabola is non-negative
+        Ordering::Greater => {
+            overlap(range, Some(range))?.checked_sub(overlap(range, cap(-a, -b, -c, false)?)?)
+        }
+        Ordering::Equal => overlap(range, linear(b, c)),
+    }
+}
+
+// Integers where a * x^2 + b * x + c with a < 0 is positive, or non-negative when
+// not `strict`. That is always a single interval, possibly empty. The outer `None`
+// is overflow
+fn cap(a: i128, b: i128, c: i128, strict: bool) -> Option<Option<(i128, i128)>> {
+    let holds = |x: i128| {
+        let y = a
+            .checked_mul(x)?
+            .checked_mul(x)?
+            .checked_add(b.checked_mul(x)?)?
+            .checked_add(c)?;
+        Some(y > 0 || (!strict && y == 0))
+    };
+
+    let discriminant = b
+        .checked_mul(b)?
+        .checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if discriminant < 0 {
+        return Some(None);
+    }
+    let root = discriminant.isqrt();
+
+    // With a < 0, rounding the square root down moves both estimates less than half
+    // a step inwards from the real roots, so each bound is within a step of them
+    let mut lo = floor_div(-b + root, 2 * a);
+    for _ in 0..2 {
+        if holds(lo)? {
+            break;
+        }
+        lo += 1;
+    }
+    if !holds(lo)? {
+        return Some(None);
+    }
+    while holds(lo - 1)? {
+        lo -= 1;
+    }
+
+    let mut hi = -floor_div(b + root, 2 * a);
+    while !holds(hi)? {
+        hi -= 1;
+    }
+    while holds(hi + 1)? {
+        hi += 1;
+    }
+
+    Some(Some((lo, hi)))
+}
+
+// Integers where b * x + c > 0
+fn linear(b: i128, c: i128) -> Option<(i128, i128)> {
+    match b.cmp(&0) {
+        Ordering::Equal => (c > 0).then_some((i128::MIN, i128::MAX)),
+        Ordering::Greater => Some((floor_div(-c, b) + 1, i128::MAX)),
+        Ordering::Less => Some((i128::MIN, -floor_div(-c, -b) - 1)),
+    }
+}
+
+// Number of integers in both, or `None` if that is all 2^64 of a full i64 range
+fn overlap((start, end): (i128, i128), interval: Option<(i128, i128)>) -> Option<u64> {
+    interval.map_or(Some(0), |(lo, hi)| {
+        u64::try_from((hi.min(end) - lo.max(start) + 1).max(0)).ok()
+    })
+}
+
+fn floor_div(n: i128, d: i128) -> i128 {
+    let q = n / d;
+    if n % d != 0 && (n < 0) != (d < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::math::{
+        checked_count_quadratic_above, checked_extended_gcd, checked_lcm, count_quadratic_above,
+        crt, extended_gcd, gcd, lcm, mod_inverse, mod_pow, modulo,
+    };
+    use proptest::prelude::*;
+
+    fn count_brute_force(a: i64, b: i64, c: i64, threshold: i64, lo: i64, hi: i64) -> u64 {
+        (lo..=hi)
+            .filter(|&x| a * x * x + b * x + c > threshold)
+            .count() as u64
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0usize, 7), 7);
+        assert_eq!(gcd(0u8, 0), 0);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(0u64, 6), 0);
+        // a * b alone would overflow, the result does not
+        assert_eq!(lcm(u64::MAX / 3, 3), u64::MAX / 3 * 3);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 0), (0, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+
+        assert_eq!(extended_gcd(i64::MIN, 1), (1, 0, 1));
+        assert_eq!(checked_extended_gcd(i64::MIN, 3).map(|(g, ..)| g), Some(1));
+        assert_eq!(checked_extended_gcd(i64::MIN, 0), None);
+        assert_eq!(checked_extended_gcd(0, i64::MIN), None);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(modulo(-7, 3), 2);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(i64::MAX - 1, 2, i64::MAX), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Non-coprime moduli
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_count_quadratic_above() {
+        // Holding the button for x of 30ms goes x * (30 - x) millimetres
+        assert_eq!(count_quadratic_above(-1, 30, 0, 200, 0..=30), 9);
+        assert_eq!(
+            count_quadratic_above(-1, 71530, 0, 940200, 0..=71530),
+            71503
+        );
+        // Roots at exactly 10 and 20 do not count
+        assert_eq!(count_quadratic_above(-1, 30, 0, 200, 10..=10), 0);
+        assert_eq!(count_quadratic_above(1, 0, 0, 4, -10..=10), 16);
+        assert_eq!(count_quadratic_above(0, 2, 0, 3, -10..=10), 9);
+        assert_eq!(count_quadratic_above(0, 0, 1, 0, -10..=10), 21);
+        assert_eq!(count_quadratic_above(-1, 0, 0, 0, -10..=10), 0);
+    }
+
+    #[test]
+    fn test_count_quadratic_above_overflow() {
+        // The discriminant alone is about 2^129
+        let (min, max) = (i64::MIN, i64::MAX);
+        assert_eq!(
+            checked_count_quadratic_above(max, 0, max, min, 0..=10),
+            None
+        );
+        assert_eq!(
+            checked_count_quadratic_above(min, 0, min, max, 0..=10),
+            None
+        );
+        // Every i64 is one more than fits in u64
+        assert_eq!(checked_count_quadratic_above(0, 0, 1, 0, min..=max), None);
+        assert_eq!(
+            checked_count_quadratic_above(0, 0, 1, 0, min + 1..=max),
+            Some(u64::MAX)
+        );
+        // Large coefficients are fine while the quadratic can be evaluated near its roots
+        assert_eq!(
+            checked_count_quadratic_above(-1, 1 << 40, 0, 0, 0..=1 << 40),
+            Some((1 << 40) - 1)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_count_quadratic_above(
+            a in -20i64..=20,
+            b in -1000i64..=1000,
+            c in -10_000i64..=10_000,
+            threshold in -10_000i64..=10_000,
+            lo in -200i64..=200,
+            len in 0i64..=400,
+        ) {
+            prop_assert_eq!(
+                count_quadratic_above(a, b, c, threshold, lo..=lo + len),
+                count_brute_force(a, b, c, threshold, lo, lo + len)
+            );
+        }
+    }
+}
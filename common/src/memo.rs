@@ -0,0 +1,191 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+
+/// Cache for a recursive function, threaded through the recursion by hand:
+///
+/// ```ignore
+/// fn paths<'a>(node: &'a str, graph: &'a Graph, memo: &mut Memo<&'a str, usize>) -> usize {
+///     memo.get(node, |memo| graph[node].iter().map(|n| paths(n, graph, memo)).sum())
+/// }
+/// ```
+///
+/// Keys are cloned into the cache, so borrowed or interned keys keep that cheap. Keys
+/// still being computed are tracked to catch cycles. The `Debug` output shows how
+/// well the cache did rather than its contents.
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    in_progress: HashSet<K>,
+    stats: Stats,
+}
+
+/// How often a [`Memo`] was asked for a value it had, did not have, or was still
+/// computing
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub cycles: usize,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            in_progress: HashSet::new(),
+            stats: Stats::default(),
+        }
+    }
+
+    /// The cached value for `key`, or `compute(self)` stored under it.
+    ///
+    /// Panics if computing `key` needs `key` again
+    pub fn get<F>(&mut self, key: K, compute: F) -> V
+    where
+        K: Debug,
+        F: FnOnce(&mut Self) -> V,
+    {
+        self.lookup(key, compute)
+            .unwrap_or_else(|key| panic!("cycle through {key:?}"))
+    }
+
+    /// Like [`Memo::get`], but a key reached again while it is being computed gives
+    /// `on_cycle` instead, e.g. 0 when counting paths that must not loop.
+    ///
+    /// A value computed while a cycle was cut short depends on the way it was reached,
+    /// so it is not cached and is computed again next time. Only values from the
+    /// acyclic parts of a graph are reused
+    pub fn get_or<F>(&mut self, key: K, on_cycle: V, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        self.lookup(key, compute).unwrap_or(on_cycle)
+    }
+
+    // The key back on a cycle
+    fn lookup<F>(&mut self, key: K, compute: F) -> Result<V, K>
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return Ok(value.clone());
+        }
+
+        if !self.in_progress.insert(key.clone()) {
+            self.stats.cycles += 1;
+            return Err(key);
+        }
+
+        self.stats.misses += 1;
+        let cycles = self.stats.cycles;
+        let value = compute(self);
+        self.in_progress.remove(&key);
+        if self.stats.cycles == cycles {
+            self.cache.insert(key, value.clone());
+        }
+
+        Ok(value)
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Number of cached values
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+impl<K, V> Debug for Memo<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Memo")
+            .field("entries", &self.cache.len())
+            .field("hits", &self.stats.hits)
+            .field("misses", &self.stats.misses)
+            .field("cycles", &self.stats.cycles)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::memo::{Memo, Stats};
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get(n, |memo| match n {
+            0 | 1 => n,
+            _ => fibonacci(n - 1, memo) + fibonacci(n - 2, memo),
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91,
+                cycles: 0
+            }
+        );
+        assert_eq!(
+            format!("{memo:?}"),
+            "Memo { entries: 91, hits: 88, misses: 91, cycles: 0 }"
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        // a -> b -> a, b -> c
+        let next = |node: &str| match node {
+            "a" => vec!["b"],
+            "b" => vec!["a", "c"],
+            _ => vec![],
+        };
+
+        fn reachable<'a>(
+            node: &'a str,
+            next: &impl Fn(&str) -> Vec<&'a str>,
+            memo: &mut Memo<&'a str, usize>,
+        ) -> usize {
+            memo.get_or(node, 0, |memo| {
+                1 + next(node)
+                    .into_iter()
+                    .map(|n| reachable(n, next, memo))
+                    .sum::<usize>()
+            })
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(reachable("a", &next, &mut memo), 3);
+        assert_eq!(memo.stats().cycles, 1);
+
+        // b was cut short at a the first time, so it is not reused from there
+        assert_eq!(memo.len(), 1);
+        assert_eq!(reachable("b", &next, &mut memo), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle through 0")]
+    fn test_cycle_panics() {
+        fn looping(n: u8, memo: &mut Memo<u8, u8>) -> u8 {
+            memo.get(n, |memo| looping(1 - n, memo))
+        }
+        looping(0, &mut Memo::new());
+    }
+}
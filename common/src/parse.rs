@@ -0,0 +1,541 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Position in the text being parsed. Cheap to copy, so a parser can be rewound by
+/// restoring an earlier copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Input { src, pos: 0 }
+    }
+
+    /// Byte offset from the start of the text
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Text that has not been consumed yet
+    pub fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.pos >= self.src.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    /// Skips `count` bytes, stopping at the end of the text. Carries on to the end of a
+    /// character it would otherwise stop inside, so `rest` stays valid on non-ASCII text
+    pub fn advance(&mut self, count: usize) {
+        self.pos = (self.pos + count).min(self.src.len());
+        while !self.src.is_char_boundary(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    /// 1-based line and column of the current position
+    pub fn position(&self) -> (usize, usize) {
+        let consumed = &self.src.as_bytes()[..self.pos];
+        let line = consumed.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = consumed
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        (line, self.pos - line_start + 1)
+    }
+
+    /// Error at the current position
+    pub fn error(&self, expected: impl Into<Expected>) -> ParseError {
+        ParseError {
+            offset: self.pos,
+            expected: expected.into(),
+            position: None,
+        }
+    }
+
+    // Consumes the longest prefix whose bytes match `pred`
+    fn take_bytes(&mut self, pred: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        let len = self.src.as_bytes()[start..]
+            .iter()
+            .take_while(|&&b| pred(b))
+            .count();
+        self.pos += len;
+        // Stop short of a split character rather than panicking on non-ASCII input
+        while !self.src.is_char_boundary(self.pos) {
+            self.pos -= 1;
+        }
+        &self.src[start..self.pos]
+    }
+}
+
+/// What a parser was looking for. Parsers fail all the time while alternatives are
+/// tried, so this is only turned into text if the error is shown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    /// Exactly this text, shown quoted
+    Literal(&'static str),
+    Text(Cow<'static, str>),
+}
+
+impl From<&'static str> for Expected {
+    fn from(text: &'static str) -> Self {
+        Expected::Text(Cow::Borrowed(text))
+    }
+}
+
+impl From<String> for Expected {
+    fn from(text: String) -> Self {
+        Expected::Text(Cow::Owned(text))
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Literal(lit) => write!(f, "{lit:?}"),
+            Expected::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset from the start of the text
+    pub offset: usize,
+    pub expected: Expected,
+    /// 1-based line and column of `offset`. Only worked out by [`ParseError::locate`]
+    /// once parsing has given up, as finding them means scanning the text
+    pub position: Option<(usize, usize)>,
+}
+
+impl ParseError {
+    /// Fills in the line and column from the text that was being parsed
+    pub fn locate(self, src: &str) -> Self {
+        let position = Input {
+            src,
+            pos: self.offset,
+        }
+        .position();
+        ParseError {
+            position: Some(position),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(
+                f,
+                "expected {} at line {line}, column {column}",
+                self.expected
+            ),
+            None => write!(f, "expected {} at byte {}", self.expected, self.offset),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// Anything that can consume a `T` from the front of an [`Input`].
+///
+/// Parsers leave the input untouched when they fail, so alternatives can be tried
+/// one after another.
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &mut Input<'a>) -> ParseResult<T>;
+
+    /// Parses the whole of `src`, allowing trailing whitespace. Errors come with
+    /// their line and column
+    fn parse_all(&self, src: &'a str) -> ParseResult<T> {
+        let mut input = Input::new(src);
+        let value = self.parse(&mut input).map_err(|e| e.locate(src))?;
+        input.take_bytes(|b| b.is_ascii_whitespace());
+
+        if input.is_eof() {
+            Ok(value)
+        } else {
+            Err(input.error("end of input").locate(src))
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&mut Input<'a>) -> ParseResult<T>,
+{
+    fn parse(&self, input: &mut Input<'a>) -> ParseResult<T> {
+        self(input)
+    }
+}
+
+// Runs `f`, rewinding the input if it fails part way through
+fn attempt<'a, T>(
+    input: &mut Input<'a>,
+    f: impl FnOnce(&mut Input<'a>) -> ParseResult<T>,
+) -> ParseResult<T> {
+    let start = *input;
+    f(input).inspect_err(|_| *input = start)
+}
+
+/// Exactly `lit`
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &mut Input<'a>| {
+        if input.rest().starts_with(lit) {
+            let matched = &input.rest()[..lit.len()];
+            input.advance(lit.len());
+            Ok(matched)
+        } else {
+            Err(input.error(Expected::Literal(lit)))
+        }
+    }
+}
+
+/// Zero or more bytes matching `pred`
+pub fn take_while<'a>(pred: impl Fn(u8) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: &mut Input<'a>| Ok(input.take_bytes(&pred))
+}
+
+/// One or more bytes matching `pred`
+pub fn take_while1<'a>(
+    expected: &'static str,
+    pred: impl Fn(u8) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |input: &mut Input<'a>| {
+        let matched = input.take_bytes(&pred);
+        if matched.is_empty() {
+            Err(input.error(expected))
+        } else {
+            Ok(matched)
+        }
+    }
+}
+
+/// Spaces and tabs, but not newlines
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    take_while(|b| b == b' ' || b == b'\t')
+}
+
+/// A run of ASCII letters, digits and underscores
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a word", |b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Unsigned integer in decimal
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        attempt(input, |input| {
+            let digits = take_while1("a digit", |b| b.is_ascii_digit()).parse(input)?;
+            digits
+                .parse()
+                .map_err(|_| input.error(format!("a number that fits, not {digits}")))
+        })
+    }
+}
+
+/// Integer in decimal with an optional leading `-` or `+`
+pub fn signed<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| {
+        attempt(input, |input| {
+            let start = input.offset();
+            if matches!(input.peek(), Some(b'-' | b'+')) {
+                input.advance(1);
+            }
+            take_while1("a digit", |b| b.is_ascii_digit()).parse(input)?;
+
+            let number = &input.src[start..input.offset()];
+            number
+                .parse()
+                .map_err(|_| input.error(format!("a number that fits, not {number}")))
+        })
+    }
+}
+
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |input: &mut Input<'a>| parser.parse(input).map(&f)
+}
+
+/// `None` instead of an error when `parser` does not match
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &mut Input<'a>| Ok(parser.parse(input).ok())
+}
+
+/// `first`, or `second` if `first` does not match
+pub fn either<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &mut Input<'a>| first.parse(input).or_else(|_| second.parse(input))
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &mut Input<'a>| {
+        attempt(input, |input| {
+            Ok((first.parse(input)?, second.parse(input)?))
+        })
+    }
+}
+
+/// `parser`, after `prefix` whose value is dropped
+pub fn preceded<'a, P, T>(
+    prefix: impl Parser<'a, P>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    map(pair(prefix, parser), |(_, value)| value)
+}
+
+/// `parser`, followed by `suffix` whose value is dropped
+pub fn terminated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, S>,
+) -> impl Parser<'a, T> {
+    map(pair(parser, suffix), |(value, _)| value)
+}
+
+/// `first` and `second` with `separator` between them, whose value is dropped
+pub fn separated_pair<'a, A, S, B>(
+    first: impl Parser<'a, A>,
+    separator: impl Parser<'a, S>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    pair(terminated(first, separator), second)
+}
+
+/// `parser` between `open` and `close`
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    parser: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, T> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Zero or more `item`s with `separator` between them
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &mut Input<'a>| {
+        let mut items = Vec::new();
+        let Ok(first) = item.parse(input) else {
+            return Ok(items);
+        };
+        items.push(first);
+
+        loop {
+            let before = *input;
+            match separator.parse(input).and_then(|_| item.parse(input)) {
+                Ok(next) => items.push(next),
+                Err(_) => {
+                    *input = before;
+                    return Ok(items);
+                }
+            }
+        }
+    }
+}
+
+/// Like [`separated`], but needs at least one `item`
+pub fn separated1<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    let items = separated(item, separator);
+    move |input: &mut Input<'a>| {
+        let start = *input;
+        let parsed = items.parse(input)?;
+        if parsed.is_empty() {
+            Err(start.error("at least one item"))
+        } else {
+            Ok(parsed)
+        }
+    }
+}
+
+/// Integers separated by spaces, tabs or commas, e.g. `1 2 3` or `7,-1`
+pub fn numbers<'a, T: FromStr>() -> impl Parser<'a, Vec<T>> {
+    separated(
+        signed(),
+        take_while1("a separator", |b| b" \t,".contains(&b)),
+    )
+}
+
+/// One `line` per line of input. The newline after the last line is left alone, so
+/// a blank line can still separate what follows
+pub fn lines<'a, T>(line: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(line, literal("\n"))
+}
+
+/// Blocks separated by blank lines, each parsed by `block`
+pub fn blocks<'a, T>(block: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(block, literal("\n\n"))
+}
+
+/// `key`, then `separator` with optional spaces around it, then `value`, as in
+/// `svr: aaa bbb` or `AAA = (BBB, CCC)`
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(
+        terminated(key, delimited(spaces(), literal(separator), spaces())),
+        value,
+    )
+}
+
+/// Rectangular grid of single byte cells, one row per line
+pub fn grid<'a, T>(cell: impl Fn(u8) -> Option<T>) -> impl Parser<'a, Vec<Vec<T>>> {
+    move |input: &mut Input<'a>| {
+        attempt(input, |input| {
+            let mut rows: Vec<Vec<T>> = Vec::new();
+
+            while !input.is_eof() && input.peek() != Some(b'\n') {
+                let mut row = Vec::new();
+                while let Some(b) = input.peek().filter(|&b| b != b'\n') {
+                    row.push(cell(b).ok_or_else(|| input.error("a grid cell"))?);
+                    input.advance(1);
+                }
+
+                if let Some(first) = rows.first() {
+                    if row.len() != first.len() {
+                        return Err(input.error(format!("a row of {} cells", first.len())));
+                    }
+                }
+                rows.push(row);
+                // A blank line ends the grid, leaving it for whatever follows
+                if input.rest().starts_with("\n\n") {
+                    break;
+                }
+                input.advance(1);
+            }
+
+            Ok(rows)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse::{
+        blocks, grid, key_value, lines, literal, numbers, pair, preceded, separated1, signed,
+        terminated, unsigned, word, Input, ParseError, Parser,
+    };
+
+    #[test]
+    fn test_integers() {
+        let mut input = Input::new("123abc");
+        assert_eq!(unsigned::<u32>().parse(&mut input), Ok(123));
+        assert_eq!(input.rest(), "abc");
+
+        assert_eq!(signed::<i64>().parse_all("-42"), Ok(-42));
+        assert_eq!(signed::<i64>().parse_all("+7"), Ok(7));
+        assert!(unsigned::<u8>().parse_all("256").is_err());
+
+        // A failed parse does not consume anything
+        let mut input = Input::new("-x");
+        assert!(signed::<i64>().parse(&mut input).is_err());
+        assert_eq!(input.offset(), 0);
+    }
+
+    #[test]
+    fn test_sequences() {
+        let mul = preceded(
+            literal("mul("),
+            pair(
+                terminated(unsigned::<u32>(), literal(",")),
+                terminated(unsigned(), literal(")")),
+            ),
+        );
+        assert_eq!(mul.parse_all("mul(2,4)"), Ok((2, 4)));
+
+        let mut input = Input::new("mul(2,4]");
+        assert!(mul.parse(&mut input).is_err());
+        assert_eq!(input.offset(), 0);
+
+        assert_eq!(numbers::<i64>().parse_all("1 -2,  3"), Ok(vec![1, -2, 3]));
+        assert_eq!(
+            separated1(word(), literal(", ")).parse_all("AAA, BBB"),
+            Ok(vec!["AAA", "BBB"])
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let node = key_value(word(), ":", numbers::<u32>());
+        assert_eq!(
+            lines(node).parse_all("a: 1 2\nb: 3\n"),
+            Ok(vec![("a", vec![1, 2]), ("b", vec![3])])
+        );
+
+        assert_eq!(
+            blocks(lines(unsigned::<u32>())).parse_all("1\n2\n\n3\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let cell = |b| match b {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        };
+
+        assert_eq!(
+            grid(cell).parse_all("#.\n.#\n"),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+
+        assert_eq!(
+            grid(cell).parse_all("#.\n.x\n"),
+            Err(ParseError {
+                offset: 4,
+                expected: "a grid cell".into(),
+                position: Some((2, 2)),
+            })
+        );
+        assert!(grid(cell).parse_all("#.\n.\n").is_err());
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut input = Input::new("é+ü");
+        input.advance(1);
+        assert_eq!(input.rest(), "+ü");
+        input.advance(2);
+        assert!(input.is_eof());
+        input.advance(1);
+        assert_eq!(input.rest(), "");
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = lines(unsigned::<u32>()).parse_all("1\n2\nx\n").unwrap_err();
+        assert_eq!(err.position, Some((3, 1)));
+        assert_eq!(err.to_string(), "expected end of input at line 3, column 1");
+
+        // Below the top level there is only the offset, until the error is located
+        let mut input = Input::new("ab\ncd");
+        input.advance(4);
+        let err = literal("x").parse(&mut input).unwrap_err();
+        assert_eq!(err.to_string(), "expected \"x\" at byte 4");
+        assert_eq!(
+            err.locate("ab\ncd").to_string(),
+            "expected \"x\" at line 2, column 2"
+        );
+    }
+}
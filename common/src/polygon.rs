@@ -0,0 +1,291 @@
+use crate::math::gcd;
+
+/// Polygon vertex or query point on the integer lattice
+pub type Vertex = (i64, i64);
+
+/// Where a point lies relative to a polygon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+impl Location {
+    /// Inside or on the boundary
+    pub fn is_covered(&self) -> bool {
+        !matches!(self, Location::Outside)
+    }
+}
+
+fn edges(polygon: &[Vertex]) -> impl Iterator<Item = (Vertex, Vertex)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+// Twice the signed area of the triangle (a, b, p): positive when p is left of a -> b
+fn cross(a: Vertex, b: Vertex, p: Vertex) -> i128 {
+    (b.0 - a.0) as i128 * (p.1 - a.1) as i128 - (p.0 - a.0) as i128 * (b.1 - a.1) as i128
+}
+
+fn on_segment(a: Vertex, b: Vertex, p: Vertex) -> bool {
+    cross(a, b, p) == 0
+        && (a.0.min(b.0)..=a.0.max(b.0)).contains(&p.0)
+        && (a.1.min(b.1)..=a.1.max(b.1)).contains(&p.1)
+}
+
+/// Twice the signed area by the shoelace formula. Positive for counter-clockwise
+/// vertices in a y-up frame, which is clockwise on screen where y grows downwards
+pub fn signed_double_area(polygon: &[Vertex]) -> i64 {
+    edges(polygon).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum()
+}
+
+/// Twice the area, so polygons with half-integer area stay exact
+pub fn double_area(polygon: &[Vertex]) -> i64 {
+    signed_double_area(polygon).abs()
+}
+
+/// Number of lattice points on the boundary
+pub fn boundary_points(polygon: &[Vertex]) -> i64 {
+    edges(polygon).map(|(a, b)| gcd(b.0 - a.0, b.1 - a.1)).sum()
+}
+
+/// Number of lattice points strictly inside, by Pick's theorem `A = I + B / 2 - 1`
+pub fn interior_points(polygon: &[Vertex]) -> i64 {
+    (double_area(polygon) - boundary_points(polygon) + 2) / 2
+}
+
+/// Number of lattice points inside or on the boundary, e.g. the tiles a dug out loop covers
+pub fn covered_points(polygon: &[Vertex]) -> i64 {
+    interior_points(polygon) + boundary_points(polygon)
+}
+
+/// Whether `point` lies on one of the edges
+pub fn on_boundary(polygon: &[Vertex], point: Vertex) -> bool {
+    edges(polygon).any(|(a, b)| on_segment(a, b, point))
+}
+
+/// How many times the polygon winds counter-clockwise around `point`, which must
+/// not lie on the boundary
+pub fn winding_number(polygon: &[Vertex], point: Vertex) -> i64 {
+    edges(polygon)
+        .map(|(a, b)| {
+            if a.1 <= point.1 && b.1 > point.1 && cross(a, b, point) > 0 {
+                1
+            } else if a.1 > point.1 && b.1 <= point.1 && cross(a, b, point) < 0 {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Point in polygon by winding number. Self-overlapping polygons count a point as
+/// inside wherever they wind around it at all
+pub fn locate(polygon: &[Vertex], point: Vertex) -> Location {
+    if on_boundary(polygon, point) {
+        Location::OnBoundary
+    } else if winding_number(polygon, point) != 0 {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Point in polygon by casting a ray towards positive x and counting crossings.
+/// Self-overlapping polygons follow the even-odd rule
+pub fn locate_ray_casting(polygon: &[Vertex], point: Vertex) -> Location {
+    let mut inside = false;
+
+    for (a, b) in edges(polygon) {
+        if on_segment(a, b, point) {
+            return Location::OnBoundary;
+        }
+
+        // Half-open in y, so a ray through a vertex only counts one of its edges
+        if (a.1 > point.1) != (b.1 > point.1) {
+            let side = cross(a, b, point);
+            if (b.1 > a.1 && side > 0) || (b.1 < a.1 && side < 0) {
+                inside = !inside;
+            }
+        }
+    }
+
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Polygon whose edges are all horizontal or vertical
+#[derive(Debug, Clone)]
+pub struct RectilinearPolygon {
+    /// `(y, x_min, x_max)` of every horizontal edge
+    pub horizontal: Vec<(i64, i64, i64)>,
+    /// `(x, y_min, y_max)` of every vertical edge
+    pub vertical: Vec<(i64, i64, i64)>,
+}
+
+impl RectilinearPolygon {
+    /// Splits the edges by orientation, or returns `None` if an edge is diagonal
+    pub fn new(polygon: &[Vertex]) -> Option<Self> {
+        let mut horizontal = Vec::new();
+        let mut vertical = Vec::new();
+
+        for (a, b) in edges(polygon) {
+            if a.1 == b.1 {
+                horizontal.push((a.1, a.0.min(b.0), a.0.max(b.0)));
+            } else if a.0 == b.0 {
+                vertical.push((a.0, a.1.min(b.1), a.1.max(b.1)));
+            } else {
+                return None;
+            }
+        }
+
+        Some(RectilinearPolygon {
+            horizontal,
+            vertical,
+        })
+    }
+
+    pub fn locate(&self, (x, y): Vertex) -> Location {
+        let on_edge = self
+            .horizontal
+            .iter()
+            .any(|&(ey, x_min, x_max)| ey == y && (x_min..=x_max).contains(&x))
+            || self
+                .vertical
+                .iter()
+                .any(|&(ex, y_min, y_max)| ex == x && (y_min..=y_max).contains(&y));
+
+        if on_edge {
+            return Location::OnBoundary;
+        }
+
+        // Only vertical edges can cross a horizontal ray
+        let crossings = self
+            .vertical
+            .iter()
+            .filter(|&&(ex, y_min, y_max)| ex > x && y_min <= y && y < y_max)
+            .count();
+
+        if crossings % 2 == 1 {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Sorted, disjoint, inclusive x ranges of the lattice points on row `y` that are
+    /// inside or on the boundary
+    pub fn row_coverage(&self, y: i64) -> Vec<(i64, i64)> {
+        // With integer vertices, the interior just above and just below the row is
+        // the same as on the lines y - 1/2 and y + 1/2, and the row's covered points are
+        // exactly the closure of those two
+        let mut spans = self.spans_below(y - 1);
+        spans.extend(self.spans_below(y));
+        spans.sort_unstable();
+
+        spans
+            .into_iter()
+            .fold(Vec::new(), |mut merged, (start, end)| {
+                match merged.last_mut() {
+                    Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+                    _ => merged.push((start, end)),
+                }
+                merged
+            })
+    }
+
+    // Interior x ranges on the line y + 1/2, closed at the crossing edges
+    fn spans_below(&self, y: i64) -> Vec<(i64, i64)> {
+        let mut crossings = self
+            .vertical
+            .iter()
+            .filter(|&&(_, y_min, y_max)| y_min <= y && y < y_max)
+            .map(|&(x, _, _)| x)
+            .collect::<Vec<_>>();
+        crossings.sort_unstable();
+
+        crossings.chunks_exact(2).map(|c| (c[0], c[1])).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::polygon::{
+        boundary_points, covered_points, double_area, interior_points, locate, locate_ray_casting,
+        signed_double_area, winding_number, Location, RectilinearPolygon,
+    };
+
+    // 4x4 square with a 2x2 bite taken out of the top right
+    const NOTCHED: &[(i64, i64)] = &[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)];
+
+    #[test]
+    fn test_area() {
+        let triangle = [(0, 0), (4, 0), (0, 3)];
+        assert_eq!(double_area(&triangle), 12);
+        assert_eq!(signed_double_area(&triangle), 12);
+        assert_eq!(signed_double_area(&[(0, 0), (0, 3), (4, 0)]), -12);
+        assert_eq!(double_area(NOTCHED), 24);
+    }
+
+    #[test]
+    fn test_lattice_points() {
+        let triangle = [(0, 0), (4, 0), (0, 3)];
+        assert_eq!(boundary_points(&triangle), 8);
+        assert_eq!(interior_points(&triangle), 3);
+
+        assert_eq!(boundary_points(NOTCHED), 16);
+        assert_eq!(interior_points(NOTCHED), 5);
+        assert_eq!(covered_points(NOTCHED), 21);
+    }
+
+    #[test]
+    fn test_locate() {
+        let cases = [
+            ((1, 1), Location::Inside),
+            ((3, 3), Location::Inside),
+            ((3, 1), Location::Outside),
+            ((2, 1), Location::OnBoundary),
+            ((4, 4), Location::OnBoundary),
+            ((5, 2), Location::Outside),
+            // A ray from here passes through the (2, 2) and (4, 2) vertices
+            ((1, 2), Location::Inside),
+        ];
+
+        let rectilinear = RectilinearPolygon::new(NOTCHED).unwrap();
+        for (point, expected) in cases {
+            assert_eq!(locate(NOTCHED, point), expected, "{point:?}");
+            assert_eq!(locate_ray_casting(NOTCHED, point), expected, "{point:?}");
+            assert_eq!(rectilinear.locate(point), expected, "{point:?}");
+        }
+
+        let clockwise = NOTCHED.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(winding_number(NOTCHED, (1, 1)), 1);
+        assert_eq!(winding_number(&clockwise, (1, 1)), -1);
+        assert_eq!(locate(&clockwise, (1, 1)), Location::Inside);
+    }
+
+    #[test]
+    fn test_rectilinear() {
+        assert!(RectilinearPolygon::new(&[(0, 0), (4, 0), (0, 3)]).is_none());
+
+        let rectilinear = RectilinearPolygon::new(NOTCHED).unwrap();
+        assert_eq!(rectilinear.row_coverage(0), vec![(0, 2)]);
+        assert_eq!(rectilinear.row_coverage(1), vec![(0, 2)]);
+        assert_eq!(rectilinear.row_coverage(2), vec![(0, 4)]);
+        assert_eq!(rectilinear.row_coverage(4), vec![(0, 4)]);
+        assert_eq!(rectilinear.row_coverage(5), vec![]);
+
+        let covered = (-1..=5)
+            .flat_map(|y| rectilinear.row_coverage(y))
+            .map(|(start, end)| end - start + 1)
+            .sum::<i64>();
+        assert_eq!(covered, covered_points(NOTCHED));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::math::gcd;
+use crate::Answer;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Exact fraction over `i128`, always in lowest terms with a positive denominator,
+/// so equal values compare and hash equal.
+///
+/// Operations cancel common factors before multiplying, but can still overflow on
+/// numbers that are large to begin with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numer: i128,
+    denom: i128,
+}
+
+impl Ratio {
+    pub const ZERO: Ratio = Ratio { numer: 0, denom: 1 };
+    pub const ONE: Ratio = Ratio { numer: 1, denom: 1 };
+
+    /// `numer / denom` in lowest terms. Panics if `denom` is zero
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert_ne!(denom, 0, "zero denominator");
+
+        let divisor = gcd(numer, denom) * denom.signum();
+        Ratio {
+            numer: numer / divisor,
+            denom: denom / divisor,
+        }
+    }
+
+    pub fn from_integer(n: i128) -> Self {
+        Ratio { numer: n, denom: 1 }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    /// Always positive
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    /// The value as an integer, if it is one
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    /// Largest integer not above the value
+    pub fn floor(&self) -> i128 {
+        self.numer.div_euclid(self.denom)
+    }
+
+    /// Smallest integer not below the value
+    pub fn ceil(&self) -> i128 {
+        -(-*self).floor()
+    }
+
+    pub fn abs(&self) -> Self {
+        Ratio {
+            numer: self.numer.abs(),
+            denom: self.denom,
+        }
+    }
+
+    /// `1 / self`. Panics on zero
+    pub fn recip(&self) -> Self {
+        Ratio::new(self.denom, self.numer)
+    }
+}
+
+impl Default for Ratio {
+    fn default() -> Self {
+        Ratio::ZERO
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Ratio {
+            fn from(n: $t) -> Self {
+                Ratio::from_integer(n as i128)
+            }
+        })*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, usize);
+
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Ratio {
+        Ratio {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl Add for Ratio {
+    type Output = Ratio;
+
+    fn add(self, rhs: Ratio) -> Ratio {
+        // Over the lcm of the denominators rather than their product
+        let g = gcd(self.denom, rhs.denom);
+        let numer = self.numer * (rhs.denom / g) + rhs.numer * (self.denom / g);
+        Ratio::new(numer, self.denom / g * rhs.denom)
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Ratio;
+
+    fn sub(self, rhs: Ratio) -> Ratio {
+        self + -rhs
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, rhs: Ratio) -> Ratio {
+        // Cancel across before multiplying to keep the products small
+        let a = gcd(self.numer, rhs.denom);
+        let b = gcd(rhs.numer, self.denom);
+        Ratio::new(
+            (self.numer / a) * (rhs.numer / b),
+            (self.denom / b) * (rhs.denom / a),
+        )
+    }
+}
+
+impl Div for Ratio {
+    type Output = Ratio;
+
+    /// Panics when dividing by zero
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Ratio) -> Ratio {
+        self * rhs.recip()
+    }
+}
+
+macro_rules! impl_assign {
+    ($($trait:ident $method:ident $op:tt),*) => {
+        $(impl $trait for Ratio {
+            fn $method(&mut self, rhs: Ratio) {
+                *self = *self $op rhs;
+            }
+        })*
+    };
+}
+
+impl_assign!(
+    AddAssign add_assign +,
+    SubAssign sub_assign -,
+    MulAssign mul_assign *,
+    DivAssign div_assign /
+);
+
+impl Sum for Ratio {
+    fn sum<I: Iterator<Item = Ratio>>(iter: I) -> Ratio {
+        iter.fold(Ratio::ZERO, Add::add)
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Ratio) -> Ordering {
+        // Denominators are positive, so cross-multiplying keeps the order
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Ratio) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// Integral values that fit in an `i64` become [`Answer::I64`], anything else is
+/// handed back unchanged
+impl TryFrom<Ratio> for Answer {
+    type Error = Ratio;
+
+    fn try_from(value: Ratio) -> Result<Self, Ratio> {
+        value
+            .to_integer()
+            .and_then(|n| i64::try_from(n).ok())
+            .map(Answer::I64)
+            .ok_or(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rational::Ratio;
+    use crate::Answer;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Ratio::new(6, -4), Ratio::new(-3, 2));
+        assert_eq!(Ratio::new(-6, -4).numer(), 3);
+        assert_eq!(Ratio::new(0, -5), Ratio::ZERO);
+        assert_eq!(Ratio::new(-7, 2).floor(), -4);
+        assert_eq!(Ratio::new(-7, 2).ceil(), -3);
+        assert_eq!(Ratio::new(8, 4).to_integer(), Some(2));
+        assert_eq!(Ratio::new(1, 3).to_string(), "1/3");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let third = Ratio::new(1, 3);
+        let half = Ratio::new(1, 2);
+
+        assert_eq!(third + half, Ratio::new(5, 6));
+        assert_eq!(third - half, Ratio::new(-1, 6));
+        assert_eq!(third * half, Ratio::new(1, 6));
+        assert_eq!(third / half, Ratio::new(2, 3));
+        assert_eq!((0..6).map(|_| third).sum::<Ratio>(), Ratio::from(2));
+
+        let mut x = Ratio::from(3);
+        x /= Ratio::from(-9);
+        assert_eq!(x, -third);
+
+        assert!(third < half);
+        assert!(-half < -third);
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::try_from(Ratio::new(10, 5)), Ok(Answer::I64(2)));
+        assert_eq!(Answer::try_from(Ratio::new(1, 2)), Err(Ratio::new(1, 2)));
+    }
+}
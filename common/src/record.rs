@@ -0,0 +1,231 @@
+use crate::parse::{literal, Input, ParseError, ParseResult, Parser};
+
+/// Derive macro for [`Record`], driven by `#[aoc(format = "...")]`
+pub use aoc_derive::Record;
+
+/// A value with a fixed one line layout, such as `AAA = (BBB, CCC)`.
+///
+/// Usually derived, see [`aoc_derive::Record`].
+pub trait Record<'a>: Sized {
+    /// Parses one record from the front of `input`, stopping at the end of its line.
+    /// May leave `input` part way through on failure, [`record`] rewinds it
+    fn parse_record(input: &mut Input<'a>) -> ParseResult<Self>;
+}
+
+/// Types a single `{field}` of a record format can be parsed into.
+///
+/// The error is a description of what was expected, e.g. `a u32`.
+pub trait FromField<'a>: Sized {
+    fn from_field(s: &'a str) -> Result<Self, String>;
+}
+
+macro_rules! impl_from_str {
+    ($($t:ty),*) => {
+        $(impl<'a> FromField<'a> for $t {
+            fn from_field(s: &'a str) -> Result<Self, String> {
+                s.parse()
+                    .map_err(|_| format!("a {}, not {s:?}", stringify!($t)))
+            }
+        })*
+    };
+}
+
+impl_from_str!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f64, char);
+
+impl<'a> FromField<'a> for &'a str {
+    fn from_field(s: &'a str) -> Result<Self, String> {
+        Ok(s)
+    }
+}
+
+impl<'a> FromField<'a> for String {
+    fn from_field(s: &'a str) -> Result<Self, String> {
+        Ok(s.to_string())
+    }
+}
+
+/// Items separated by whitespace or commas. Use `#[aoc(sep = "...")]` on the field
+/// for anything else
+impl<'a, T: FromField<'a>> FromField<'a> for Vec<T> {
+    fn from_field(s: &'a str) -> Result<Self, String> {
+        s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|item| !item.is_empty())
+            .map(T::from_field)
+            .collect()
+    }
+}
+
+/// Parser for a single [`Record`]
+pub fn record<'a, R: Record<'a>>() -> impl Parser<'a, R> {
+    move |input: &mut Input<'a>| {
+        let start = *input;
+        R::parse_record(input).inspect_err(|_| *input = start)
+    }
+}
+
+/// One [`Record`] per line of `src`. Unlike [`crate::parse::lines`], this reports
+/// why a bad line failed to parse instead of stopping before it
+pub fn records<'a, R: Record<'a>>(src: &'a str) -> ParseResult<Vec<R>> {
+    let mut input = Input::new(src);
+    let mut items = Vec::new();
+
+    while !input.is_eof() {
+        let mut line = || {
+            items.push(R::parse_record(&mut input)?);
+            if !input.is_eof() {
+                literal("\n").parse(&mut input)?;
+            }
+            Ok(())
+        };
+        line().map_err(|e: ParseError| e.locate(src))?;
+    }
+
+    Ok(items)
+}
+
+/// Items of `s` separated by `sep`, each trimmed and parsed with [`FromField`]
+pub fn list<'a, T: FromField<'a>>(s: &'a str, sep: &str) -> Result<Vec<T>, String> {
+    s.split(sep)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(T::from_field)
+        .collect()
+}
+
+/// [`FromField`] for a nested record, which has to span the whole of `s`
+pub fn from_field<'a, R: Record<'a>>(s: &'a str, name: &str) -> Result<R, String> {
+    record()
+        .parse_all(s)
+        .map_err(|e| format!("a {name} ({e} of the field)"))
+}
+
+// Consumes the text of a field: up to `until` if given, otherwise to the end of the
+// line. Returns it trimmed along with its position, for errors
+fn take_field<'a>(input: &mut Input<'a>, until: Option<&str>) -> ParseResult<(&'a str, Input<'a>)> {
+    let rest = input.rest();
+    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+
+    let len = match until {
+        Some(lit) => line
+            .find(lit)
+            .ok_or_else(|| input.error(format!("{lit:?} later on the line")))?,
+        None => line.len(),
+    };
+
+    let text = &line[..len];
+    let mut start = *input;
+    start.advance(text.len() - text.trim_start().len());
+    input.advance(len);
+
+    Ok((text.trim(), start))
+}
+
+/// Parses one field of a derived [`Record`], used by the generated code
+pub fn field<'a, T: FromField<'a>>(
+    input: &mut Input<'a>,
+    until: Option<&str>,
+    name: &str,
+) -> ParseResult<T> {
+    let (text, start) = take_field(input, until)?;
+    T::from_field(text).map_err(|expected| start.error(format!("{expected} for `{name}`")))
+}
+
+/// Like [`field`], for a list with its own separator
+pub fn list_field<'a, T: FromField<'a>>(
+    input: &mut Input<'a>,
+    until: Option<&str>,
+    name: &str,
+    sep: &str,
+) -> ParseResult<Vec<T>> {
+    let (text, start) = take_field(input, until)?;
+    list(text, sep).map_err(|expected| start.error(format!("{expected} in `{name}`")))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse::{ParseError, Parser};
+    use crate::record::{record, records, Record};
+
+    #[derive(Debug, PartialEq, Record)]
+    #[aoc(format = "{name} = ({left}, {right})")]
+    struct Node<'a> {
+        name: &'a str,
+        left: &'a str,
+        right: &'a str,
+    }
+
+    #[derive(Debug, PartialEq, Record)]
+    #[aoc(format = "Card {id}: {winning} | {numbers}")]
+    struct Card {
+        id: u32,
+        winning: Vec<u8>,
+        numbers: Vec<u8>,
+    }
+
+    #[derive(Debug, PartialEq, Record)]
+    #[aoc(format = "{{{count} {colour}}}")]
+    struct Cubes {
+        count: u32,
+        colour: String,
+    }
+
+    #[derive(Debug, PartialEq, Record)]
+    #[aoc(format = "Game {id}: {draws}")]
+    struct Game {
+        id: u32,
+        #[aoc(sep = ";")]
+        draws: Vec<Cubes>,
+    }
+
+    #[test]
+    fn test_records() {
+        let nodes = records::<Node>("AAA = (BBB, CCC)\nBBB = (AAA, ZZZ)\n").unwrap();
+        assert_eq!(
+            nodes[1],
+            Node {
+                name: "BBB",
+                left: "AAA",
+                right: "ZZZ"
+            }
+        );
+
+        let card = record::<Card>()
+            .parse_all("Card   3:  1 21 | 69 82  1")
+            .unwrap();
+        assert_eq!(card.id, 3);
+        assert_eq!(card.winning, vec![1, 21]);
+        assert_eq!(card.numbers, vec![69, 82, 1]);
+    }
+
+    #[test]
+    fn test_nested() {
+        let game = record::<Game>()
+            .parse_all("Game 2: {1 blue}; {3 green}")
+            .unwrap();
+        assert_eq!(game.id, 2);
+        assert_eq!(
+            game.draws[1],
+            Cubes {
+                count: 3,
+                colour: "green".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = records::<Card>("Card 1: 1 | 2\nCard x: 1 | 2").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                offset: 19,
+                expected: "a u32, not \"x\" for `id`".into(),
+                position: Some((2, 6)),
+            }
+        );
+
+        let error = records::<Node>("AAA = (BBB CCC)").unwrap_err();
+        assert_eq!(error.position, Some((1, 8)));
+        assert_eq!(error.expected.to_string(), "\", \" later on the line");
+    }
+}
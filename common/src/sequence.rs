@@ -0,0 +1,133 @@
+use crate::rational::Ratio;
+
+/// The leading diagonal of the finite difference table of `values`: `values[0]`, its
+/// first difference, second difference and so on, stopping before the first level
+/// that is all zeros.
+///
+/// These are the coefficients of the sequence in Newton's forward form,
+/// `f(n) = sum(diagonal[k] * binomial(n, k))`.
+pub fn newton_coefficients(values: &[i64]) -> Vec<i64> {
+    let mut level = values.to_vec();
+    let mut coefficients = Vec::new();
+
+    while level.iter().any(|&v| v != 0) {
+        coefficients.push(level[0]);
+        // Differences in place, so the table is never stored as a whole
+        for i in 0..level.len() - 1 {
+            level[i] = level[i + 1] - level[i];
+        }
+        level.pop();
+    }
+
+    coefficients
+}
+
+/// Degree of the polynomial that generates `values`, if there are enough of them to
+/// be sure: the differences at that degree have to be constant and there have to be
+/// at least two of them. The zero sequence counts as degree zero
+pub fn degree(values: &[i64]) -> Option<usize> {
+    let mut level = values.to_vec();
+
+    for d in 0..values.len().saturating_sub(1) {
+        if level.windows(2).all(|w| w[0] == w[1]) {
+            return Some(d);
+        }
+        level = level.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+
+    None
+}
+
+/// Value at `index` of the polynomial sequence that starts with `values`, where
+/// `values[i]` is at index `i`. The index can be negative or far past the end.
+///
+/// Panics if the result does not fit in an `i64`
+pub fn extrapolate(values: &[i64], index: i64) -> i64 {
+    let n = index as i128;
+    let mut binomial = 1_i128;
+    let mut total = 0_i128;
+
+    for (k, &coefficient) in newton_coefficients(values).iter().enumerate() {
+        if k > 0 {
+            // binomial(n, k) from binomial(n, k - 1), which always divides exactly
+            binomial = binomial * (n - k as i128 + 1) / k as i128;
+        }
+        total += coefficient as i128 * binomial;
+    }
+
+    i64::try_from(total).expect("extrapolated value does not fit in an i64")
+}
+
+/// Value `distance` steps after the last of `values`
+pub fn forward(values: &[i64], distance: i64) -> i64 {
+    extrapolate(values, values.len() as i64 - 1 + distance)
+}
+
+/// Value `distance` steps before the first of `values`
+pub fn backward(values: &[i64], distance: i64) -> i64 {
+    extrapolate(values, -distance)
+}
+
+/// Value at `x` of the lowest degree polynomial through `points`, for samples that
+/// are not evenly spaced, e.g. at steps 65, 196 and 327. Returns `None` if the `x`
+/// coordinates are not distinct
+pub fn lagrange(points: &[(i64, i64)], x: i64) -> Option<Ratio> {
+    let mut sum = Ratio::ZERO;
+
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term = Ratio::from(yi);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                if xi == xj {
+                    return None;
+                }
+                term *= Ratio::new((x - xj) as i128, (xi - xj) as i128);
+            }
+        }
+        sum += term;
+    }
+
+    Some(sum)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rational::Ratio;
+    use crate::sequence::{backward, degree, extrapolate, forward, lagrange, newton_coefficients};
+
+    #[test]
+    fn test_extrapolate() {
+        let squares = [0, 1, 4, 9, 16];
+        assert_eq!(newton_coefficients(&squares), vec![0, 1, 2]);
+        assert_eq!(forward(&squares, 1), 25);
+        assert_eq!(forward(&squares, 5), 81);
+        assert_eq!(backward(&squares, 3), 9);
+        assert_eq!(extrapolate(&squares, 1_000_000), 1_000_000_000_000);
+
+        assert_eq!(forward(&[10, 13, 16, 21, 30, 45], 1), 68);
+        assert_eq!(backward(&[10, 13, 16, 21, 30, 45], 1), 5);
+        assert_eq!(forward(&[0, 0, 0], 10), 0);
+    }
+
+    #[test]
+    fn test_degree() {
+        assert_eq!(degree(&[3, 3]), Some(0));
+        assert_eq!(degree(&[0, 3, 6, 9]), Some(1));
+        assert_eq!(degree(&[1, 3, 6, 10, 15, 21]), Some(2));
+        // Three points fit a parabola, but nothing confirms it
+        assert_eq!(degree(&[1, 3, 6]), None);
+        assert_eq!(degree(&[7]), None);
+    }
+
+    #[test]
+    fn test_lagrange() {
+        // y = 2x^2 - 3x + 1
+        let f = |x: i64| 2 * x * x - 3 * x + 1;
+        let points = [65, 196, 327].map(|x| (x, f(x)));
+        assert_eq!(lagrange(&points, 26501365), Some(Ratio::from(f(26501365))));
+        assert_eq!(lagrange(&points, -4), Some(Ratio::from(f(-4))));
+
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Some(Ratio::new(1, 2)));
+        assert_eq!(lagrange(&[(0, 0), (0, 1)], 1), None);
+    }
+}
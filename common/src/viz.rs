@@ -0,0 +1,525 @@
+use crate::Solution;
+use std::fmt::Write as _;
+use std::io;
+use std::io::Write;
+use std::ops::Range;
+use std::time::Duration;
+
+/// A 24-bit colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(90, 90, 90);
+    pub const RED: Rgb = Rgb(230, 60, 50);
+    pub const GREEN: Rgb = Rgb(60, 180, 75);
+    pub const YELLOW: Rgb = Rgb(250, 210, 40);
+    pub const BLUE: Rgb = Rgb(50, 120, 230);
+
+    /// Linear blend, `t = 0` giving `self` and `t = 1` giving `other`
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colour number `i` of a small palette of easily told apart colours, cycling
+pub fn palette(i: usize) -> Rgb {
+    const COLOURS: [Rgb; 8] = [
+        Rgb(230, 60, 50),
+        Rgb(60, 180, 75),
+        Rgb(50, 120, 230),
+        Rgb(250, 210, 40),
+        Rgb(245, 130, 50),
+        Rgb(145, 30, 180),
+        Rgb(70, 220, 220),
+        Rgb(240, 50, 230),
+    ];
+    COLOURS[i % COLOURS.len()]
+}
+
+/// Grid of pixels, `(0, 0)` in the top left. Drawing outside the canvas is ignored, so
+/// shapes may be cut off at the edges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Colour at `(x, y)`, if that is on the canvas
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    /// Every pixel on the straight line between the two points, both ends included
+    pub fn line(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize), colour: Rgb) {
+        // Bresenham, stepping along whichever axis is longer
+        let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+        loop {
+            self.set(x as usize, y as usize, colour);
+            if (x, y) == (x1, y1) {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Fills the rectangle with the two corners given, inclusive
+    pub fn fill_rect(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize), colour: Rgb) {
+        for y in y0.min(y1)..=y0.max(y1) {
+            for x in x0.min(x1)..=x0.max(x1) {
+                self.set(x, y, colour);
+            }
+        }
+    }
+
+    /// Draws with 24-bit ANSI colours, two pixels per character using half blocks, so
+    /// pixels come out roughly square. Colours are only set when they change
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for y in (0..self.height).step_by(2) {
+            self.ansi_run(&mut out, y, 0..self.width);
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+
+    /// Turns a terminal showing `previous` as drawn by [`Canvas::to_ansi`] from the top
+    /// left into one showing this canvas, redrawing only the characters that changed.
+    /// Both canvases must be the same size
+    pub fn to_ansi_since(&self, previous: &Canvas) -> String {
+        assert_eq!(
+            (self.width, self.height),
+            (previous.width, previous.height),
+            "canvases have different sizes"
+        );
+
+        let mut out = String::new();
+        for y in (0..self.height).step_by(2) {
+            let changed = |x: usize| {
+                self.get(x, y) != previous.get(x, y) || self.get(x, y + 1) != previous.get(x, y + 1)
+            };
+
+            let mut x = 0;
+            while x < self.width {
+                if !changed(x) {
+                    x += 1;
+                    continue;
+                }
+                let end = (x..self.width).find(|&x| !changed(x)).unwrap_or(self.width);
+
+                // Terminal rows and columns count from one
+                let _ = write!(out, "\x1b[{};{}H", y / 2 + 1, x + 1);
+                self.ansi_run(&mut out, y, x..end);
+                out.push_str("\x1b[0m");
+                x = end;
+            }
+        }
+        out
+    }
+
+    // Half blocks for the pixels `xs` of rows `y` and `y + 1`
+    fn ansi_run(&self, out: &mut String, y: usize, xs: Range<usize>) {
+        let (mut fg, mut bg) = (None, None);
+        for x in xs {
+            let top = self.pixels[y * self.width + x];
+            if fg != Some(top) {
+                let Rgb(r, g, b) = top;
+                let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+                fg = Some(top);
+            }
+
+            let bottom = self.get(x, y + 1);
+            if bg != Some(bottom) {
+                match bottom {
+                    Some(Rgb(r, g, b)) => {
+                        let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+                    }
+                    None => out.push_str("\x1b[49m"),
+                }
+                bg = Some(bottom);
+            }
+            out.push('▀');
+        }
+    }
+
+    /// SVG document with every pixel drawn as a `scale` sided square. Runs of the same
+    /// colour along a row share one rectangle
+    pub fn to_svg(&self, scale: usize) -> String {
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+            self.width * scale,
+            self.height * scale,
+            self.width,
+            self.height
+        );
+
+        for (y, row) in self.pixels.chunks(self.width.max(1)).enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{}\" height=\"1\" fill=\"{}\"/>",
+                    run.len(),
+                    run[0].hex()
+                );
+                x += run.len();
+            }
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Writes a PNG with every pixel drawn as a `scale` sided square
+    pub fn write_png(&self, writer: impl Write, scale: usize) -> io::Result<()> {
+        let scale = scale.max(1);
+        let mut encoder = png::Encoder::new(
+            writer,
+            (self.width * scale) as u32,
+            (self.height * scale) as u32,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.scaled_rgb(scale)))
+            .map_err(io::Error::other)
+    }
+
+    // Red, green and blue bytes of every pixel, each pixel repeated `scale` times
+    // across and down
+    fn scaled_rgb(&self, scale: usize) -> Vec<u8> {
+        self.pixels
+            .chunks(self.width.max(1))
+            .flat_map(|row| {
+                let line = row
+                    .iter()
+                    .flat_map(|&pixel| std::iter::repeat_n(pixel, scale))
+                    .flat_map(|Rgb(r, g, b)| [r, g, b])
+                    .collect::<Vec<_>>();
+                std::iter::repeat_n(line, scale).flatten()
+            })
+            .collect()
+    }
+}
+
+/// Frames an iterative solver emits as it runs, one per step, for playing back
+/// afterwards. Every frame should be the same size
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    frames: Vec<Canvas>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Recording::default()
+    }
+
+    pub fn push(&mut self, frame: Canvas) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Canvas] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes a looping GIF showing each frame for `delay`, with every pixel drawn as
+    /// a `scale` sided square. Frames with more than 256 colours are quantized
+    pub fn write_gif(&self, writer: impl Write, scale: usize, delay: Duration) -> io::Result<()> {
+        let scale = scale.max(1);
+        let (width, height) = self.size(scale)?;
+
+        let mut encoder =
+            gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        // GIF delays are in hundredths of a second
+        let delay = (delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+        for canvas in &self.frames {
+            let mut frame =
+                gif::Frame::from_rgb_speed(width, height, &canvas.scaled_rgb(scale), 10);
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an asciinema v2 cast that draws each frame in turn, `delay` apart, by
+    /// redrawing what changed since the one before
+    pub fn write_cast(&self, mut writer: impl Write, delay: Duration) -> io::Result<()> {
+        let (width, height) = self.size(1)?;
+        writeln!(
+            writer,
+            "{{\"version\": 2, \"width\": {width}, \"height\": {}}}",
+            (height as usize).div_ceil(2)
+        )?;
+
+        for (i, canvas) in self.frames.iter().enumerate() {
+            let picture = match i.checked_sub(1) {
+                Some(previous) => canvas.to_ansi_since(&self.frames[previous]),
+                None => format!("\x1b[2J\x1b[H{}", canvas.to_ansi()),
+            };
+            writeln!(
+                writer,
+                "[{:.3}, \"o\", \"{}\"]",
+                (delay * i as u32).as_secs_f64(),
+                json_escape(&picture)
+            )?;
+        }
+        Ok(())
+    }
+
+    // Scaled size of the frames, which GIF limits to 16 bits each way
+    fn size(&self, scale: usize) -> io::Result<(u16, u16)> {
+        let first = self.frames.first().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "recording has no frames")
+        })?;
+        if self
+            .frames
+            .iter()
+            .any(|f| (f.width, f.height) != (first.width, first.height))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames have different sizes",
+            ));
+        }
+
+        let fit = |n: usize| {
+            u16::try_from(n * scale)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frames are too large"))
+        };
+        Ok((fit(first.width)?, fit(first.height)?))
+    }
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Maps puzzle coordinates onto a canvas, for puzzles whose coordinates are far too
+/// large to draw one pixel per unit. Both axes share the same scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    min: (i64, i64),
+    scale: f64,
+    width: usize,
+    height: usize,
+}
+
+impl Viewport {
+    /// Smallest viewport holding every point, with its longer side `size` pixels
+    pub fn fit(points: impl IntoIterator<Item = (i64, i64)>, size: usize) -> Self {
+        let (mut min, mut max) = ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN));
+        for (x, y) in points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        if min.0 > max.0 {
+            min = (0, 0);
+            max = (0, 0);
+        }
+
+        let span = (max.0 - min.0).max(max.1 - min.1).max(1) as f64;
+        let scale = (size.max(1) - 1) as f64 / span;
+
+        Viewport {
+            min,
+            scale,
+            width: ((max.0 - min.0) as f64 * scale) as usize + 1,
+            height: ((max.1 - min.1) as f64 * scale) as usize + 1,
+        }
+    }
+
+    /// Empty canvas of the viewport's size
+    pub fn canvas(&self, background: Rgb) -> Canvas {
+        Canvas::new(self.width, self.height, background)
+    }
+
+    /// Pixel the point falls on
+    pub fn pixel(&self, (x, y): (i64, i64)) -> (usize, usize) {
+        let scaled = |v: i64, min: i64| ((v - min) as f64 * self.scale).round().max(0.0) as usize;
+        (scaled(x, self.min.0), scaled(y, self.min.1))
+    }
+
+    /// Point at the middle of the pixel
+    pub fn point(&self, (x, y): (usize, usize)) -> (i64, i64) {
+        let unscaled = |v: usize, min: i64| min + (v as f64 / self.scale).round() as i64;
+        (unscaled(x, self.min.0), unscaled(y, self.min.1))
+    }
+}
+
+/// Solutions that can draw what they computed for an input
+pub trait Visualize: Solution {
+    fn visualize(&self, input: &str) -> Canvas;
+}
+
+/// Solutions that can replay how they got to their answer, pushing a frame for each
+/// step of their simulation
+pub trait Animate: Solution {
+    fn animate(&self, input: &str, recording: &mut Recording);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::viz::{Canvas, Recording, Rgb, Viewport};
+    use std::time::Duration;
+
+    #[test]
+    fn test_drawing() {
+        let mut canvas = Canvas::new(5, 4, Rgb::BLACK);
+        canvas.line((0, 0), (4, 2), Rgb::WHITE);
+        canvas.fill_rect((3, 3), (9, 3), Rgb::RED);
+
+        let drawn = (0..4)
+            .map(|y| {
+                (0..5)
+                    .map(|x| match canvas.get(x, y).unwrap() {
+                        Rgb::WHITE => '#',
+                        Rgb::RED => 'r',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(drawn, vec!["#....", ".##..", "...##", "...rr"]);
+        assert_eq!(canvas.get(5, 0), None);
+    }
+
+    #[test]
+    fn test_output() {
+        let mut canvas = Canvas::new(3, 3, Rgb::BLACK);
+        canvas.set(1, 1, Rgb::WHITE);
+
+        let svg = canvas.to_svg(10);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.contains("<rect x=\"1\" y=\"1\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>"));
+
+        // Two rows of half blocks, the second with no row below it
+        let ansi = canvas.to_ansi();
+        assert_eq!(ansi.lines().count(), 2);
+        assert_eq!(ansi.matches('▀').count(), 6);
+
+        let mut png = Vec::new();
+        canvas.write_png(&mut png, 2).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn test_viewport() {
+        let view = Viewport::fit([(1000, 500), (3000, 1500), (2000, 1000)], 201);
+        let canvas = view.canvas(Rgb::BLACK);
+        assert_eq!((canvas.width(), canvas.height()), (201, 101));
+        assert_eq!(view.pixel((1000, 500)), (0, 0));
+        assert_eq!(view.pixel((3000, 1500)), (200, 100));
+        assert_eq!(view.point((100, 50)), (2000, 1000));
+    }
+
+    #[test]
+    fn test_recording() {
+        let mut recording = Recording::new();
+        let mut canvas = Canvas::new(2, 3, Rgb::BLACK);
+        recording.push(canvas.clone());
+        canvas.set(0, 0, Rgb::WHITE);
+        recording.push(canvas);
+
+        let mut gif = Vec::new();
+        recording
+            .write_gif(&mut gif, 3, Duration::from_millis(50))
+            .unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let mut cast = Vec::new();
+        recording
+            .write_cast(&mut cast, Duration::from_millis(250))
+            .unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        let lines = cast.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], r#"{"version": 2, "width": 2, "height": 2}"#);
+        assert!(lines[1].starts_with(r#"[0.000, "o", "\u001b[2J\u001b[H\u001b[38;2;0;0;0m"#));
+        assert_eq!(
+            lines[2],
+            r#"[0.250, "o", "\u001b[1;1H\u001b[38;2;255;255;255m\u001b[48;2;0;0;0m▀\u001b[0m"]"#
+        );
+
+        recording.push(Canvas::new(1, 1, Rgb::BLACK));
+        assert!(recording.write_gif(Vec::new(), 1, Duration::ZERO).is_err());
+        assert!(Recording::new()
+            .write_cast(Vec::new(), Duration::ZERO)
+            .is_err());
+    }
+}
@@ -0,0 +1,6 @@
+// Mistakes in `#[derive(Record)]` should be reported where they are, not as errors in
+// the generated code
+#[test]
+fn test_record_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
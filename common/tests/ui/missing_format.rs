@@ -0,0 +1,8 @@
+use common::record::Record;
+
+#[derive(Record)]
+struct Card {
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use common::record::Record;
+
+#[derive(Record)]
+#[aoc(format = "{name}", spearator = ",")]
+struct Node<'a> {
+    name: &'a str,
+}
+
+fn main() {}
@@ -0,0 +1,132 @@
+use clap::{Args, Subcommand};
+use common::crypt::{self, Key};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct DataArgs {
+    #[command(subcommand)]
+    command: DataCommand,
+
+    /// Only this year's inputs rather than every year in data/
+    // Named apart from the top-level `--year`, whose default would otherwise apply
+    #[arg(short, long, global = true, id = "only_year")]
+    year: Option<u32>,
+}
+
+#[derive(Subcommand, Debug)]
+enum DataCommand {
+    /// Print a new key to share with the team, in AOC_KEY or a .aoc-key file
+    Key,
+
+    /// Write each data/<year>/<dd>.txt to an encrypted <dd>.txt.enc, which can be
+    /// committed in its place
+    Encrypt,
+
+    /// Write each data/<year>/<dd>.txt.enc back out as a plain <dd>.txt
+    Decrypt,
+}
+
+pub fn run(args: DataArgs) {
+    if let DataCommand::Key = args.command {
+        println!("{}", Key::generate().to_hex());
+        return;
+    }
+
+    let key = match Key::load() {
+        Ok(key) => key,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    let inputs = match inputs(args.year) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            println!("Could not list the inputs in data/: {e}");
+            return;
+        }
+    };
+
+    for input in inputs {
+        let encrypted = crypt::encrypted_path(&input);
+        let (from, to) = match args.command {
+            DataCommand::Encrypt => (&input, &encrypted),
+            _ => (&encrypted, &input),
+        };
+        if !from.exists() {
+            continue;
+        }
+
+        let result = match args.command {
+            DataCommand::Encrypt => encrypt(&key, from, to),
+            _ => decrypt(&key, from, to),
+        };
+        match result {
+            Ok(true) => println!("Wrote {}", to.display()),
+            Ok(false) => {}
+            Err(e) => println!("Could not write {}: {e}", to.display()),
+        }
+    }
+}
+
+/// Every `data/<year>/<dd>.txt` there is a plain or encrypted copy of
+fn inputs(year: Option<u32>) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = Vec::new();
+    for dir in fs::read_dir("data")? {
+        let dir = dir?.path();
+        let name = dir.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let Some(found) = name.parse::<u32>().ok().filter(|_| dir.is_dir()) else {
+            continue;
+        };
+        if year.is_some_and(|year| year != found) {
+            continue;
+        }
+
+        for file in fs::read_dir(&dir)? {
+            let file = file?.path();
+            let name = file
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            let day = name
+                .strip_suffix(".enc")
+                .unwrap_or(name)
+                .strip_suffix(".txt");
+            if let Some(day) = day.filter(|d| d.len() == 2 && d.parse::<u32>().is_ok()) {
+                inputs.push(dir.join(format!("{day}.txt")));
+            }
+        }
+    }
+
+    inputs.sort();
+    inputs.dedup();
+    Ok(inputs)
+}
+
+/// Encrypts an input, unless its encrypted copy already holds the same, so that
+/// running this again does not change every file. Returns whether it wrote anything
+fn encrypt(key: &Key, input: &Path, encrypted: &Path) -> io::Result<bool> {
+    let plain = fs::read(input)?;
+    let current = fs::read(encrypted)
+        .ok()
+        .and_then(|sealed| key.decrypt(&sealed).ok());
+    if current.as_ref() == Some(&plain) {
+        return Ok(false);
+    }
+
+    fs::write(encrypted, key.encrypt(&plain))?;
+    Ok(true)
+}
+
+fn decrypt(key: &Key, encrypted: &Path, input: &Path) -> io::Result<bool> {
+    let plain = key.decrypt(&fs::read(encrypted)?)?;
+    if fs::read(input).ok().as_ref() == Some(&plain) {
+        return Ok(false);
+    }
+
+    fs::write(input, plain)?;
+    Ok(true)
+}
@@ -0,0 +1,257 @@
+use clap::Args;
+use scraper::{ElementRef, Html, Selector};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct ExamplesArgs {
+    /// Year of the puzzle
+    #[arg(short, long, default_value_t = 2023)]
+    year: u32,
+
+    /// Day of the puzzle
+    #[arg(short, long, default_value_t = 1)]
+    day: u32,
+
+    /// Puzzle description saved from the site as HTML, with part two too if it is
+    /// unlocked
+    page: PathBuf,
+
+    /// Replace the day's examples if it has any already, hand-written ones included
+    #[arg(long)]
+    force: bool,
+}
+
+/// The examples a puzzle description works through
+#[derive(Debug, PartialEq)]
+pub struct Examples {
+    /// Every different `<pre><code>` block, in page order
+    pub blocks: Vec<String>,
+    /// For each part, the block it is worked through on and the answer it comes to
+    pub answers: [Option<(usize, String)>; 2],
+}
+
+/// Finds the examples in a puzzle description. Each part's answer is taken to be the
+/// last emphasized code in its article, e.g. `<code><em>142</em></code>`, worked out
+/// on the closest example block before it. That guess is wrong when the page shows
+/// the example again in another form before the answer, so check what was found
+pub fn extract(html: &str) -> Examples {
+    let document = Html::parse_document(html);
+    let articles = Selector::parse("article.day-desc").unwrap();
+    let marked = Selector::parse("pre > code, code > em, em > code").unwrap();
+
+    let mut blocks = Vec::<String>::new();
+    let mut answers = [None, None];
+    let mut latest = None;
+
+    for (part, article) in document.select(&articles).take(2).enumerate() {
+        for element in article.select(&marked) {
+            let text = element.text().collect::<String>();
+            if parent_name(element) == Some("pre") {
+                // Part two often repeats the example from part one
+                latest = Some(blocks.iter().position(|b| *b == text).unwrap_or_else(|| {
+                    blocks.push(text);
+                    blocks.len() - 1
+                }));
+            } else if let Some(block) = latest.filter(|_| !inside_pre(element)) {
+                answers[part] = Some((block, text.trim().to_string()));
+            }
+        }
+    }
+
+    Examples { blocks, answers }
+}
+
+fn parent_name(element: ElementRef<'_>) -> Option<&str> {
+    element
+        .parent()
+        .and_then(ElementRef::wrap)
+        .map(|parent| parent.value().name())
+}
+
+// Emphasis inside an example block highlights part of the example, not an answer
+fn inside_pre(element: ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|a| a.value().name() == "pre")
+}
+
+pub fn run(args: ExamplesArgs) {
+    let html = match fs::read_to_string(&args.page) {
+        Ok(html) => html,
+        Err(e) => {
+            println!("Could not read {}: {e}", args.page.display());
+            return;
+        }
+    };
+
+    let examples = extract(&html);
+    if examples.answers.iter().all(Option::is_none) {
+        println!("Found no example answers in {}", args.page.display());
+        return;
+    }
+
+    let dir = PathBuf::from(format!("data/{}/examples", args.year));
+    if let Err(e) = write(&dir, args.day, &examples, args.force) {
+        println!("Could not write to {}: {e}", dir.display());
+    }
+}
+
+/// Writes the blocks the answers were worked out on as `<dd>-N.txt`, each next to a
+/// `<dd>-N.json` holding its answers as `[part one, part two]`, `null` where the
+/// page gives none. Examples the day already has are all removed first with `force`,
+/// and without it nothing is written, so hand-written ones are not overwritten and
+/// stale ones are not left behind
+fn write(dir: &Path, day: u32, examples: &Examples, force: bool) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let existing = existing(dir, day)?;
+    if !existing.is_empty() {
+        if !force {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "day {day} already has {} example files, pass --force to replace them",
+                    existing.len()
+                ),
+            ));
+        }
+        for file in &existing {
+            fs::remove_file(file)?;
+        }
+    }
+
+    let mut used = examples
+        .answers
+        .iter()
+        .flatten()
+        .map(|(block, _)| *block)
+        .collect::<Vec<_>>();
+    used.sort_unstable();
+    used.dedup();
+
+    for (n, &block) in used.iter().enumerate() {
+        let expected = examples
+            .answers
+            .clone()
+            .map(|answer| answer.filter(|(b, _)| *b == block).map(|(_, value)| value));
+
+        let name = format!("{day:02}-{}", n + 1);
+        let input = dir.join(format!("{name}.txt"));
+        fs::write(&input, &examples.blocks[block])?;
+        fs::write(
+            dir.join(format!("{name}.json")),
+            serde_json::to_string(&expected)? + "\n",
+        )?;
+
+        let describe =
+            |part: &str, answer: &Option<String>| answer.as_ref().map(|a| format!("{part} {a}"));
+        let parts = [
+            describe("part one", &expected[0]),
+            describe("part two", &expected[1]),
+        ];
+        println!(
+            "Wrote {} expecting {}",
+            input.display(),
+            parts.into_iter().flatten().collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+// The day's `<dd>-N.txt` and `<dd>-N.json` files
+fn existing(dir: &Path, day: u32) -> io::Result<Vec<PathBuf>> {
+    let prefix = format!("{day:02}-");
+    let mut files = Vec::new();
+    for file in fs::read_dir(dir)? {
+        let file = file?.path();
+        let is_example = file
+            .file_stem()
+            .and_then(|stem| stem.to_str()?.strip_prefix(&prefix)?.parse::<u32>().ok())
+            .is_some()
+            && file.extension().is_some_and(|e| e == "txt" || e == "json");
+        if is_example {
+            files.push(file);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::examples::{extract, write, Examples};
+    use std::fs;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr<em>3</em>stu8vwx
+</code></pre>
+<p>The values are <code>12</code> and <code>38</code>. Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>56506</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In this example, <em>spelled out</em> digits count too:</p>
+<pre><code>two1nine
+eight&lt;wo
+</code></pre>
+<p>Adding these together produces <em><code>29</code></em> and then <code><em>281</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(PAGE),
+            Examples {
+                blocks: vec!["1abc2\npqr3stu8vwx\n".into(), "two1nine\neight<wo\n".into()],
+                answers: [Some((0, "50".into())), Some((1, "281".into()))],
+            }
+        );
+    }
+
+    #[test]
+    fn test_part_two_reuses_example() {
+        let page = PAGE.replace("<pre><code>two1nine\neight&lt;wo\n</code></pre>", "");
+        let examples = extract(&page);
+        assert_eq!(examples.blocks.len(), 1);
+        assert_eq!(examples.answers[1], Some((0, "281".into())));
+
+        // Repeating the block is the same as referring back to it
+        let page = PAGE.replace("two1nine\neight&lt;wo", "1abc2\npqr<em>3</em>stu8vwx");
+        assert_eq!(extract(&page), examples);
+
+        // Without part two unlocked there is only one article
+        let part_one = &PAGE[..PAGE.find("<p>Your puzzle").unwrap()];
+        assert_eq!(extract(part_one).answers[1], None);
+    }
+
+    #[test]
+    fn test_write_replaces_only_with_force() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("08-3.txt"), "by hand").unwrap();
+        fs::write(dir.join("08-3.json"), "[\"6\",null]").unwrap();
+        fs::write(dir.join("09-1.txt"), "another day").unwrap();
+
+        let examples = extract(PAGE);
+        assert!(write(&dir, 8, &examples, false).is_err());
+        assert_eq!(fs::read_to_string(dir.join("08-3.txt")).unwrap(), "by hand");
+        assert!(!dir.join("08-1.txt").exists());
+
+        write(&dir, 8, &examples, true).unwrap();
+        assert!(!dir.join("08-3.txt").exists() && !dir.join("08-3.json").exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("08-1.json")).unwrap(),
+            "[\"50\",null]\n"
+        );
+        assert!(dir.join("08-2.txt").exists() && dir.join("09-1.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,9 +1,45 @@
-use clap::Parser;
+mod data;
+#[cfg(feature = "examples")]
+mod examples;
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "viz")]
+mod viz;
+
+use clap::{Args, Parser, Subcommand};
 use common::Solution;
+use std::time::{Duration, Instant};
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct AdventOfCode {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Encrypt or decrypt the puzzle inputs in data/
+    Data(data::DataArgs),
+
+    /// Save the examples and their answers from a puzzle description
+    #[cfg(feature = "examples")]
+    Examples(examples::ExamplesArgs),
+
+    /// Draw what a day's solution computed
+    #[cfg(feature = "viz")]
+    Viz(viz::VizArgs),
+
+    /// Browse, run and check every solution in a terminal dashboard
+    #[cfg(feature = "tui")]
+    Tui,
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
     /// Year to use
     #[arg(short, long, default_value_t = 2023)]
     year: u32,
@@ -15,11 +51,32 @@ struct AdventOfCode {
     /// Part specifies which part of the given day to run
     #[arg(short, long, default_value_t = 1)]
     part: u32,
+
+    /// Run the part this many times and report how long it took
+    #[arg(short, long)]
+    bench: Option<u32>,
 }
 
 fn main() {
     let args = AdventOfCode::parse();
 
+    match args.command {
+        Some(Command::Data(args)) => data::run(args),
+        #[cfg(feature = "examples")]
+        Some(Command::Examples(args)) => examples::run(args),
+        #[cfg(feature = "viz")]
+        Some(Command::Viz(args)) => viz::run(args),
+        #[cfg(feature = "tui")]
+        Some(Command::Tui) => {
+            if let Err(e) = tui::run() {
+                println!("Dashboard failed: {e}");
+            }
+        }
+        None => run(args.run),
+    }
+}
+
+fn run(args: RunArgs) {
     let solutions = get_solutions_for_year(args.year);
 
     let solution = match solutions.get((args.day as usize) - 1) {
@@ -32,13 +89,33 @@ fn main() {
 
     let data = common::load_file(args.year, args.day).unwrap();
 
-    let result = match args.part {
+    let run = || match args.part {
         1 => solution.part_one(&data),
         2 => solution.part_two(&data),
         _ => unimplemented!(),
     };
 
+    let result = run();
     println!("Result = {}", result);
+
+    if let Some(runs) = args.bench {
+        let timings = (0..runs.max(1))
+            .map(|_| {
+                let start = Instant::now();
+                run();
+                start.elapsed()
+            })
+            .collect::<Vec<Duration>>();
+
+        let total = timings.iter().sum::<Duration>();
+        println!(
+            "Ran {} times: min {:?}, mean {:?}, max {:?}",
+            timings.len(),
+            timings.iter().min().unwrap(),
+            total / timings.len() as u32,
+            timings.iter().max().unwrap()
+        );
+    }
 }
 
 fn get_solutions_for_year<'a>(year: u32) -> Vec<&'a dyn Solution> {
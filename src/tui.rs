@@ -0,0 +1,341 @@
+use crate::get_solutions_for_year;
+use common::answers::{Answers, Verdict};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::cell::RefCell;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const YEARS: [u32; 3] = [2023, 2024, 2025];
+
+// Number of worker threads running solutions. While there are any, panics off the
+// dashboard's own thread come from solutions, on the worker or on threads it started
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Browses every solution, running parts on demand and checking them against
+/// `data/answers.json`
+pub fn run() -> io::Result<()> {
+    let answers = Answers::load()?;
+
+    let mut terminal = ratatui::init();
+
+    // Panics in solutions are shown in the dashboard rather than printed over it.
+    // Anything else still goes to the hook that puts the terminal back first
+    let dashboard = thread::current().id();
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if RUNNING.load(Ordering::SeqCst) > 0 && thread::current().id() != dashboard {
+            PANIC.with(|p| *p.borrow_mut() = Some(info.to_string()));
+        } else {
+            previous(info);
+        }
+    }));
+
+    let result = Dashboard::new(answers).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    NotRun,
+    Running,
+    Done {
+        answer: String,
+        elapsed: Duration,
+        verdict: Verdict,
+    },
+    Failed(String),
+}
+
+impl Status {
+    fn symbol(&self) -> Span<'static> {
+        match self {
+            Status::NotRun => "·".dark_gray(),
+            Status::Running => "…".cyan(),
+            Status::Done { verdict, .. } => match verdict {
+                Verdict::Correct => "✓".green(),
+                Verdict::Wrong(_) => "✗".red(),
+                Verdict::Unknown => "?".yellow(),
+            },
+            Status::Failed(_) => "!".magenta(),
+        }
+    }
+}
+
+struct Entry {
+    year: u32,
+    day: u32,
+    parts: [Status; 2],
+}
+
+// What a worker sends back for one part: the entry, the part and how it went
+type Outcome = (usize, usize, Status);
+
+struct Dashboard {
+    entries: Vec<Entry>,
+    list: ListState,
+    answers: Arc<Answers>,
+    // Input of the selected day, read once on selection
+    preview: ((u32, u32), Result<String, String>),
+    scroll: u16,
+    sender: Sender<Outcome>,
+    results: Receiver<Outcome>,
+}
+
+impl Dashboard {
+    fn new(answers: Answers) -> Self {
+        let entries = YEARS
+            .iter()
+            .flat_map(|&year| {
+                (1..=get_solutions_for_year(year).len() as u32).map(move |day| Entry {
+                    year,
+                    day,
+                    parts: [Status::NotRun, Status::NotRun],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let (sender, results) = mpsc::channel();
+        let mut dashboard = Dashboard {
+            entries,
+            list: ListState::default().with_selected(Some(0)),
+            answers: Arc::new(answers),
+            preview: ((0, 0), Ok(String::new())),
+            scroll: 0,
+            sender,
+            results,
+        };
+        dashboard.load_preview();
+        dashboard
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            while let Ok((entry, part, status)) = self.results.try_recv() {
+                self.entries[entry].parts[part] = status;
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            // Wake up now and then to pick up finished runs
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Char('1') => self.start(vec![(self.selected(), 0)]),
+                KeyCode::Char('2') => self.start(vec![(self.selected(), 1)]),
+                KeyCode::Enter => self.start(vec![(self.selected(), 0), (self.selected(), 1)]),
+                KeyCode::Char('a') => self.start(
+                    (0..self.entries.len())
+                        .flat_map(|e| [(e, 0), (e, 1)])
+                        .collect(),
+                ),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                _ => {}
+            }
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.list.selected().unwrap_or(0)
+    }
+
+    fn select(&mut self, by: isize) {
+        let last = self.entries.len() - 1;
+        let index = self.selected().saturating_add_signed(by).min(last);
+        self.list.select(Some(index));
+        self.load_preview();
+    }
+
+    fn load_preview(&mut self) {
+        let Entry { year, day, .. } = self.entries[self.selected()];
+        if self.preview.0 != (year, day) {
+            let input = common::load_file(year, day).map_err(|e| e.to_string());
+            self.preview = ((year, day), input);
+            self.scroll = 0;
+        }
+    }
+
+    /// Runs the `(entry, part)` jobs one after another on a worker thread
+    fn start(&mut self, jobs: Vec<(usize, usize)>) {
+        let mut queued = Vec::new();
+        for (e, p) in jobs {
+            let entry = &mut self.entries[e];
+            if entry.parts[p] != Status::Running {
+                entry.parts[p] = Status::Running;
+                queued.push((e, p, entry.year, entry.day));
+            }
+        }
+
+        let answers = Arc::clone(&self.answers);
+        let sender = self.sender.clone();
+        RUNNING.fetch_add(1, Ordering::SeqCst);
+        thread::spawn(move || {
+            for (entry, part, year, day) in queued {
+                let status = run_part(year, day, part, &answers);
+                if sender.send((entry, part, status)).is_err() {
+                    break;
+                }
+            }
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let [days, right] =
+            Layout::horizontal([Constraint::Length(22), Constraint::Fill(1)]).areas(main);
+        let [results, input] =
+            Layout::vertical([Constraint::Length(10), Constraint::Fill(1)]).areas(right);
+
+        let items = self
+            .entries
+            .iter()
+            .map(|e| {
+                ListItem::new(Line::from(vec![
+                    format!("{} Day {:<3} ", e.year, e.day).into(),
+                    e.parts[0].symbol(),
+                    " ".into(),
+                    e.parts[1].symbol(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Solutions "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, days, &mut self.list);
+
+        let entry = &self.entries[self.selected()];
+        let lines = entry
+            .parts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, status)| part_lines(i + 1, status))
+            .collect::<Vec<_>>();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(format!(" {} Day {} ", entry.year, entry.day))),
+            results,
+        );
+
+        let (title, text) = match &self.preview.1 {
+            Ok(data) => (
+                format!(
+                    " data/{}/{:02}.txt, {} lines ",
+                    entry.year,
+                    entry.day,
+                    data.lines().count()
+                ),
+                data.as_str(),
+            ),
+            Err(e) => (" Input ".to_string(), e.as_str()),
+        };
+        frame.render_widget(
+            Paragraph::new(text)
+                .scroll((self.scroll, 0))
+                .block(Block::bordered().title(title)),
+            input,
+        );
+
+        frame.render_widget(
+            Line::from(
+                " ↑/↓ select  1/2 run part  enter run both  a run all  pgup/pgdn scroll input  q quit",
+            )
+            .dark_gray(),
+            help,
+        );
+    }
+}
+
+fn part_lines(part: usize, status: &Status) -> Vec<Line<'static>> {
+    let heading = Span::from(format!("Part {part}  ")).bold();
+    match status {
+        Status::NotRun => vec![Line::from(vec![heading, "not run".dark_gray()])],
+        Status::Running => vec![Line::from(vec![heading, "running…".cyan()])],
+        Status::Done {
+            answer,
+            elapsed,
+            verdict,
+        } => {
+            let verdict = match verdict {
+                Verdict::Correct => "✓ matches the stored answer".green(),
+                Verdict::Wrong(expected) => format!("✗ expected {expected}").red(),
+                Verdict::Unknown => "? no stored answer".yellow(),
+            };
+            vec![Line::from(vec![
+                heading,
+                answer.clone().bold(),
+                format!("  in {elapsed:?}  ").into(),
+                verdict,
+            ])]
+        }
+        Status::Failed(message) => {
+            let mut lines = vec![Line::from(vec![heading, "failed".magenta()])];
+            lines.extend(
+                message
+                    .lines()
+                    .map(|l| Line::from(l.to_string()).style(Color::Magenta)),
+            );
+            lines
+        }
+    }
+}
+
+fn run_part(year: u32, day: u32, part: usize, answers: &Answers) -> Status {
+    let data = match common::load_file(year, day) {
+        Ok(data) => data,
+        Err(e) => return Status::Failed(format!("Could not read the input: {e}")),
+    };
+
+    let solution = get_solutions_for_year(year)[day as usize - 1];
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match part {
+        0 => solution.part_one(&data),
+        _ => solution.part_two(&data),
+    }));
+    let elapsed = start.elapsed();
+
+    match result {
+        Ok(answer) => Status::Done {
+            verdict: answers.check(year, day, part as u32 + 1, &answer),
+            answer: answer.to_string(),
+            elapsed,
+        },
+        // A panic on another thread, such as one of rayon's, is only caught here once
+        // it is passed on, so the hook kept its message on that thread instead
+        Err(payload) => Status::Failed(
+            PANIC
+                .with(|p| p.borrow_mut().take())
+                .or_else(|| payload.downcast_ref::<&str>().map(|m| m.to_string()))
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panicked".into()),
+        ),
+    }
+}
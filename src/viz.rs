@@ -0,0 +1,251 @@
+use clap::Args;
+use common::viz::{Animate, Canvas, Recording, Visualize};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct VizArgs {
+    /// Year to use
+    #[arg(short, long, default_value_t = 2023)]
+    year: u32,
+
+    /// Day to draw
+    #[arg(short, long, default_value_t = 1)]
+    day: u32,
+
+    /// Write to this file instead of the terminal: .png or .svg, or with --animate
+    /// .gif or .cast
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Side of each pixel in the written file
+    #[arg(short, long, default_value_t = 4)]
+    scale: usize,
+
+    /// Draw this input rather than the day's puzzle input
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Replay the solution step by step instead of drawing where it ended up
+    #[arg(short, long)]
+    animate: bool,
+
+    /// Milliseconds between the steps of an animation
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+}
+
+pub fn run(args: VizArgs) {
+    let data = match &args.input {
+        Some(path) => common::parse_file(path),
+        None => common::load_file(args.year, args.day),
+    };
+
+    if args.animate {
+        let Some(day) = get_animation(args.year, args.day) else {
+            println!("No animation for day {} in year {}", args.day, args.year);
+            return;
+        };
+
+        let mut recording = Recording::new();
+        day.animate(&data.unwrap(), &mut recording);
+        animate(&args, &recording);
+    } else {
+        let Some(day) = get_visualization(args.year, args.day) else {
+            println!(
+                "No visualization for day {} in year {}",
+                args.day, args.year
+            );
+            return;
+        };
+
+        draw(&args, &day.visualize(&data.unwrap()));
+    }
+}
+
+fn draw(args: &VizArgs, canvas: &Canvas) {
+    let Some(path) = &args.output else {
+        print!("{}", canvas.to_ansi());
+        return;
+    };
+
+    let written = match extension(path) {
+        Some("png") => {
+            File::create(path).and_then(|file| canvas.write_png(BufWriter::new(file), args.scale))
+        }
+        Some("svg") => std::fs::write(path, canvas.to_svg(args.scale)),
+        _ => {
+            println!("Can only write .png or .svg files, not {}", path.display());
+            return;
+        }
+    };
+
+    match written {
+        Ok(()) => println!(
+            "Wrote {}x{} image to {}",
+            canvas.width() * args.scale,
+            canvas.height() * args.scale,
+            path.display()
+        ),
+        Err(e) => println!("Could not write {}: {e}", path.display()),
+    }
+}
+
+fn animate(args: &VizArgs, recording: &Recording) {
+    if recording.is_empty() {
+        println!("Nothing was recorded");
+        return;
+    }
+
+    let delay = Duration::from_millis(args.delay);
+    let Some(path) = &args.output else {
+        if let Err(e) = play(recording, delay) {
+            println!("Could not play the animation: {e}");
+        }
+        return;
+    };
+
+    let written = match extension(path) {
+        Some("gif") => File::create(path)
+            .and_then(|file| recording.write_gif(BufWriter::new(file), args.scale, delay)),
+        Some("cast") => {
+            File::create(path).and_then(|file| recording.write_cast(BufWriter::new(file), delay))
+        }
+        _ => {
+            println!("Can only write .gif or .cast files, not {}", path.display());
+            return;
+        }
+    };
+
+    match written {
+        Ok(()) => println!("Wrote {} frames to {}", recording.len(), path.display()),
+        Err(e) => println!("Could not write {}: {e}", path.display()),
+    }
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|e| e.to_str())
+}
+
+/// Plays the frames in the terminal until the last one or until quit. Space pauses,
+/// the arrow keys step while paused, + and - change the speed
+fn play(recording: &Recording, delay: Duration) -> io::Result<()> {
+    let _screen = Screen::enter()?;
+    playback(&mut io::stdout(), recording, delay)
+}
+
+/// The terminal taken over for playing, given back when dropped so that it is left
+/// usable however playing ends, errors and panics included
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = Screen;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        // Nothing more can be done if this fails
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn playback(stdout: &mut impl Write, recording: &Recording, mut delay: Duration) -> io::Result<()> {
+    let frames = recording.frames();
+    let last = frames.len() - 1;
+    let status_row = frames[0].height().div_ceil(2) as u16;
+    let (mut frame, mut paused, mut shown) = (0, false, None);
+
+    loop {
+        match shown {
+            Some(previous) => write!(stdout, "{}", frames[frame].to_ansi_since(&frames[previous]))?,
+            None => {
+                // Raw mode does not return the cursor to the start of the line on its own
+                queue!(stdout, cursor::MoveTo(0, 0))?;
+                write!(stdout, "{}", frames[frame].to_ansi().replace('\n', "\r\n"))?;
+            }
+        }
+        shown = Some(frame);
+
+        queue!(stdout, cursor::MoveTo(0, status_row))?;
+        write!(
+            stdout,
+            "frame {}/{}{}, {}ms a step  [space] pause  [←/→] step  [+/-] speed  [q] quit",
+            frame + 1,
+            last + 1,
+            if paused { " (paused)" } else { "" },
+            delay.as_millis()
+        )?;
+        queue!(stdout, terminal::Clear(ClearType::UntilNewLine))?;
+        stdout.flush()?;
+
+        // Wait for a key, or until the next frame is due while playing
+        if paused || event::poll(delay)? {
+            let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+
+            match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => {
+                    // Starting again from the end replays from the beginning
+                    if paused && frame == last {
+                        frame = 0;
+                    }
+                    paused = !paused;
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    paused = true;
+                    frame = (frame + 1).min(last);
+                }
+                KeyCode::Left | KeyCode::Char('h') => {
+                    paused = true;
+                    frame = frame.saturating_sub(1);
+                }
+                KeyCode::Home => frame = 0,
+                KeyCode::End => frame = last,
+                KeyCode::Char('+') => delay = (delay / 2).max(Duration::from_millis(5)),
+                KeyCode::Char('-') => delay = (delay * 2).min(Duration::from_secs(5)),
+                _ => {}
+            }
+        } else if frame == last {
+            paused = true;
+        } else {
+            frame += 1;
+        }
+    }
+}
+
+fn get_visualization(year: u32, day: u32) -> Option<&'static dyn Visualize> {
+    let days = match year {
+        2023 => aoc2023::VISUALIZATIONS,
+        2025 => aoc2025::VISUALIZATIONS,
+        _ => &[],
+    };
+
+    days.iter().find(|&&(d, _)| d == day).map(|&(_, v)| v)
+}
+
+fn get_animation(year: u32, day: u32) -> Option<&'static dyn Animate> {
+    let days = match year {
+        2025 => aoc2025::ANIMATIONS,
+        _ => &[],
+    };
+
+    days.iter().find(|&&(d, _)| d == day).map(|&(_, a)| a)
+}
@@ -0,0 +1,94 @@
+//! Runs every example under `data/<year>/examples` through its day's solution.
+//!
+//! `<dd>-N.txt` is an example input and `<dd>-N.json` holds the answers it gives as
+//! `[part one, part two]`, `null` for a part it is not an example of. This is the
+//! layout `aoc examples` writes. Each part is a test of its own, named like
+//! `2023::day_05::example_1::part_two`, so `cargo test --test examples 2023::day_05`
+//! runs only that day's examples.
+
+mod support;
+
+use libtest_mimic::{Arguments, Failed, Trial};
+use std::fs;
+use std::path::Path;
+use support::{solutions, solve, PARTS, YEARS};
+
+fn main() {
+    let args = Arguments::from_args();
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+
+    let trials = YEARS
+        .iter()
+        .flat_map(|&year| discover(&data, year))
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}
+
+fn discover(data: &Path, year: u32) -> Vec<Trial> {
+    let Ok(entries) = fs::read_dir(data.join(year.to_string()).join("examples")) else {
+        return Vec::new();
+    };
+
+    let mut expectations = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect::<Vec<_>>();
+    expectations.sort();
+
+    expectations
+        .into_iter()
+        .flat_map(|path| {
+            // A file that cannot be read as an example fails as a test of its own
+            // rather than being passed over
+            trials(year, &path).unwrap_or_else(|e| {
+                let name = format!("{year}::{}", path.file_name().unwrap().to_string_lossy());
+                vec![Trial::test(name, move || Err(e.into()))]
+            })
+        })
+        .collect()
+}
+
+fn trials(year: u32, path: &Path) -> Result<Vec<Trial>, String> {
+    let (day, example) = path
+        .file_stem()
+        .and_then(|stem| stem.to_str()?.split_once('-'))
+        .and_then(|(d, n)| Some((d.parse::<u32>().ok()?, n.parse::<u32>().ok()?)))
+        .filter(|&(day, _)| day > 0)
+        .ok_or_else(|| format!("{} is not named <dd>-N.json", path.display()))?;
+
+    if solutions(year).len() < day as usize {
+        return Err(format!("There is no solution for day {day} of {year}"));
+    }
+
+    let json = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let answers = serde_json::from_str::<[Option<String>; 2]>(&json)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let input = path.with_extension("txt");
+    Ok(answers
+        .into_iter()
+        .enumerate()
+        .filter_map(|(part, expected)| {
+            let (expected, input) = (expected?, input.clone());
+            let name = format!(
+                "{year}::day_{day:02}::example_{example}::part_{}",
+                PARTS[part]
+            );
+            Some(Trial::test(name, move || {
+                check(year, day, part, &input, &expected)
+            }))
+        })
+        .collect())
+}
+
+fn check(year: u32, day: u32, part: usize, input: &Path, expected: &str) -> Result<(), Failed> {
+    let data = common::parse_file(input).map_err(|e| format!("{}: {e}", input.display()))?;
+    let answer = solve(year, day, part, &data);
+
+    if answer.to_string() == expected {
+        Ok(())
+    } else {
+        Err(format!("expected {expected}, got {answer}").into())
+    }
+}
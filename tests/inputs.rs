@@ -0,0 +1,84 @@
+//! Runs both parts of every day on its real input in `data/<year>/<dd>.txt` and
+//! compares the answers with `data/answers.json`.
+//!
+//! Only built with the `full-inputs` feature, as it takes a while. Each part is a test
+//! named like `2025::day_10::part_two` that fails if the answer is wrong or takes longer
+//! than the timeout. Parts without a stored answer are ignored, but one with an answer
+//! and no input, plain or encrypted, fails, so that a missing input is not taken for a
+//! pass.
+
+mod support;
+
+use common::answers::{Answers, Verdict};
+use common::crypt::encrypted_path;
+use libtest_mimic::{Arguments, Failed, Trial};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use support::{solutions, solve, PARTS, YEARS};
+
+/// How long a part may take, in the unoptimised build `cargo test` uses by default
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn main() {
+    let args = Arguments::from_args();
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+
+    let answers = match Answers::from_file(&data.join("answers.json")) {
+        Ok(answers) => Arc::new(answers),
+        Err(e) => {
+            eprintln!("Could not read data/answers.json: {e}");
+            std::process::exit(101);
+        }
+    };
+
+    let trials = YEARS
+        .iter()
+        .flat_map(|&year| (1..=solutions(year).len() as u32).map(move |day| (year, day)))
+        .flat_map(|(year, day)| [(year, day, 0), (year, day, 1)])
+        .map(|(year, day, part)| {
+            let input = data.join(format!("{year}/{day:02}.txt"));
+            let unanswered = answers.get(year, day, part as u32 + 1).is_none();
+            let answers = Arc::clone(&answers);
+            Trial::test(
+                format!("{year}::day_{day:02}::part_{}", PARTS[part]),
+                move || check(year, day, part, &input, &answers),
+            )
+            .with_ignored_flag(unanswered)
+        })
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}
+
+fn check(year: u32, day: u32, part: usize, input: &Path, answers: &Answers) -> Result<(), Failed> {
+    if !(input.exists() || encrypted_path(input).exists()) {
+        return Err(format!(
+            "{} has an answer but no input, plain or encrypted",
+            input.display()
+        )
+        .into());
+    }
+    let data = common::parse_file(input).map_err(|e| format!("{}: {e}", input.display()))?;
+
+    // The solution runs on a thread of its own so a slow one can be given up on. It is
+    // left running, and goes when the process exits
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(solve(year, day, part, &data)));
+
+    let answer = match receiver.recv_timeout(TIMEOUT) {
+        Ok(answer) => answer,
+        Err(RecvTimeoutError::Timeout) => {
+            return Err(format!("took longer than {TIMEOUT:?}").into())
+        }
+        Err(RecvTimeoutError::Disconnected) => return Err("panicked".into()),
+    };
+
+    match answers.check(year, day, part as u32 + 1, &answer) {
+        Verdict::Correct => Ok(()),
+        Verdict::Wrong(expected) => Err(format!("expected {expected}, got {answer}").into()),
+        Verdict::Unknown => Err("no answer stored in data/answers.json".into()),
+    }
+}
@@ -0,0 +1,25 @@
+//! What the test targets that run whole solutions have in common
+
+use common::{Answer, Solution};
+
+pub const YEARS: [u32; 3] = [2023, 2024, 2025];
+
+pub const PARTS: [&str; 2] = ["one", "two"];
+
+pub fn solutions(year: u32) -> &'static [&'static dyn Solution] {
+    match year {
+        2023 => aoc2023::ALL,
+        2024 => aoc2024::ALL,
+        2025 => aoc2025::ALL,
+        _ => &[],
+    }
+}
+
+/// Runs `part` 0 or 1 of a day that has a solution
+pub fn solve(year: u32, day: u32, part: usize, input: &str) -> Answer {
+    let solution = solutions(year)[day as usize - 1];
+    match part {
+        0 => solution.part_one(input),
+        _ => solution.part_two(input),
+    }
+}